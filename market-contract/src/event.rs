@@ -0,0 +1,76 @@
+/**
+ * Để các ứng dụng (bên thứ 3) theo dõi được các
+ * hành động quản trị của Market Contract
+ */
+use std::fmt;
+
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json;
+
+/// Enum that represents the data type of the EventLog.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+#[serde(crate = "near_sdk::serde")]
+#[non_exhaustive]
+pub enum EventLogVariant {
+    OwnershipTransferStarted(Vec<OwnershipTransferLog>),
+    OwnershipTransferred(Vec<OwnershipTransferLog>),
+}
+
+/// Interface to capture data about an event
+///
+/// Arguments:
+/// * `standard`: name of standard e.g. zng-market
+/// * `version`: e.g. 1.0.0
+/// * `event`: associate event data
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EventLog {
+    pub standard: String,
+    pub version: String,
+
+    // `flatten` to not have "event": {<EventLogVariant>} in the JSON, just have the contents of {<EventLogVariant>}.
+    #[serde(flatten)]
+    pub event: EventLogVariant,
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "EVENT_JSON:{}",
+            &serde_json::to_string(self).map_err(|_| fmt::Error)?
+        ))
+    }
+}
+
+/// An event log to capture contract ownership transfer
+///
+/// Arguments
+/// * `old_owner_id`: current owner of the contract
+/// * `new_owner_id`: proposed (or accepted) owner of the contract
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnershipTransferLog {
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zng_format_ownership_transfer_started() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-market","version":"1.0.0","event":"ownership_transfer_started","data":[{"old_owner_id":"user1.near","new_owner_id":"user2.near"}]}"#;
+        let log = EventLog {
+            standard: "zng-market".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OwnershipTransferStarted(vec![OwnershipTransferLog {
+                old_owner_id: "user1.near".to_string(),
+                new_owner_id: "user2.near".to_string(),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+}
@@ -2,6 +2,15 @@ use crate::*;
 
 #[near_bindgen]
 impl Contract {
+    // Chỉ owner của contract mới được gọi các hàm quản trị
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only contract owner can call this method"
+        );
+    }
+
     pub(crate) fn internal_remove_sale(
         &mut self,
         nft_contract_id: AccountId,
@@ -6,20 +6,25 @@ use near_sdk::{
     env, ext_contract, near_bindgen, AccountId, Balance, CryptoHash, Gas, PanicOnDefault, Promise, PromiseOrValue
 };
 
+pub use crate::event::*;
 pub use crate::internal::*;
 pub use crate::nft_callback::*;
 pub use crate::sale::*;
 pub use crate::sale_view::*;
+pub use crate::upgrade::*;
 pub use crate::utils::*;
 pub use crate::ft_callback::*;
 
 // Coi như sau mỗi lần bán qua lại thì tăng storage lên 1000 bytes
 const STORAGE_PER_SALE: u128 = 1000 * env::STORAGE_PRICE_PER_BYTE;
 
+mod event;
 mod internal;
 mod nft_callback;
+mod owner;
 mod sale;
 mod sale_view;
+mod upgrade;
 mod utils;
 mod ft_callback;
 
@@ -50,11 +55,14 @@ pub struct Sale {
     pub sale_conditions: SalePrice,
 }
 
+// Note: Thay đổi layout của struct này cần migrate state, xem upgrade.rs
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
     // Owner of contract
     pub owner_id: AccountId,
+    // Owner mới đang chờ accept (chuyển quyền owner 2 bước)
+    pub pending_owner_id: Option<AccountId>,
     // Danh sách sales của token
     pub sales: UnorderedMap<ContractAndTokenId, Sale>,
     // Danh sách token_id đang được đăng bán của 1 account_id
@@ -85,8 +93,11 @@ pub enum StorageKey {
 impl Contract {
     #[init]
     pub fn new(owner_id: AccountId) -> Self {
+        write_state_version();
+
         Self {
             owner_id,
+            pending_owner_id: None,
             sales: UnorderedMap::new(StorageKey::SaleKey.try_to_vec().unwrap()),
             by_owner_id: LookupMap::new(StorageKey::ByOwnerIdKey.try_to_vec().unwrap()),
             by_contract_id: LookupMap::new(StorageKey::ByContractIdKey.try_to_vec().unwrap()),
@@ -164,3 +175,106 @@ impl Contract {
         U128(self.storage_deposit.get(&owner_id).unwrap_or(0))
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+    use near_sdk::MockedBlockchain;
+
+    fn get_context() -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(accounts(0))
+            .predecessor_account_id(accounts(0));
+
+        builder
+    }
+
+    #[test]
+    fn test_migrate_from_v1() {
+        let context = get_context();
+        testing_env!(context.build());
+
+        // --- State của bản deploy đầu tiên (chưa có versioning), có 1 account đã deposit storage
+        let mut state = ContractV1 {
+            owner_id: accounts(0).to_string(),
+            sales: UnorderedMap::new(StorageKey::SaleKey.try_to_vec().unwrap()),
+            by_owner_id: LookupMap::new(StorageKey::ByOwnerIdKey.try_to_vec().unwrap()),
+            by_contract_id: LookupMap::new(StorageKey::ByContractIdKey.try_to_vec().unwrap()),
+            storage_deposit: LookupMap::new(StorageKey::StorageDepositKey.try_to_vec().unwrap()),
+        };
+        state
+            .storage_deposit
+            .insert(&accounts(1).to_string(), &STORAGE_PER_SALE);
+        env::state_write(&state);
+
+        let contract = Contract::migrate();
+
+        assert_eq!(contract.version(), STATE_VERSION);
+        assert_eq!(contract.get_owner(), accounts(0).to_string());
+        assert_eq!(contract.get_pending_owner(), None);
+        assert_eq!(
+            contract.storage_balance_of(Some(accounts(1).to_string())),
+            U128(STORAGE_PER_SALE)
+        );
+    }
+
+    #[test]
+    fn test_migrate_from_v2() {
+        let context = get_context();
+        testing_env!(context.build());
+
+        let mut state = ContractV2 {
+            owner_id: accounts(0).to_string(),
+            pending_owner_id: Some(accounts(2).to_string()),
+            sales: UnorderedMap::new(StorageKey::SaleKey.try_to_vec().unwrap()),
+            by_owner_id: LookupMap::new(StorageKey::ByOwnerIdKey.try_to_vec().unwrap()),
+            by_contract_id: LookupMap::new(StorageKey::ByContractIdKey.try_to_vec().unwrap()),
+            storage_deposit: LookupMap::new(StorageKey::StorageDepositKey.try_to_vec().unwrap()),
+        };
+        state
+            .storage_deposit
+            .insert(&accounts(1).to_string(), &STORAGE_PER_SALE);
+        env::state_write(&state);
+        env::storage_write(STATE_VERSION_KEY, &2u16.try_to_vec().unwrap());
+
+        let contract = Contract::migrate();
+
+        assert_eq!(contract.version(), STATE_VERSION);
+        assert_eq!(contract.get_pending_owner(), Some(accounts(2).to_string()));
+        assert_eq!(
+            contract.storage_balance_of(Some(accounts(1).to_string())),
+            U128(STORAGE_PER_SALE)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown state version 3")]
+    fn test_migrate_unknown_version() {
+        let context = get_context();
+        testing_env!(context.build());
+
+        let contract = Contract::new(accounts(0).to_string());
+        env::state_write(&contract);
+        env::storage_write(STATE_VERSION_KEY, &3u16.try_to_vec().unwrap());
+
+        Contract::migrate();
+    }
+
+    #[test]
+    #[should_panic(expected = "Only contract owner can call this method")]
+    fn test_upgrade_not_owner() {
+        let mut context = get_context();
+        testing_env!(context.build());
+
+        let contract = Contract::new(accounts(0).to_string());
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+
+        contract.upgrade();
+    }
+}
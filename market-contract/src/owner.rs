@@ -0,0 +1,67 @@
+/**
+ * Chuyển quyền owner của contract theo 2 bước:
+ * - Owner hiện tại đề xuất owner mới (propose_owner)
+ * - Owner mới xác nhận (accept_owner) -> Tránh chuyển nhầm quyền cho account không tồn tại/không kiểm soát được
+ */
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    // Owner hiện tại đề xuất owner mới, đề xuất sau sẽ ghi đè đề xuất trước
+    #[payable]
+    pub fn propose_owner(&mut self, new_owner_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        assert_ne!(
+            new_owner_id, self.owner_id,
+            "New owner must be different from the current owner"
+        );
+
+        self.pending_owner_id = Some(new_owner_id.clone());
+
+        // OWNERSHIP TRANSFER STARTED LOG
+        let ownership_log: EventLog = EventLog {
+            standard: "zng-market".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OwnershipTransferStarted(vec![OwnershipTransferLog {
+                old_owner_id: self.owner_id.to_string(),
+                new_owner_id: new_owner_id.to_string(),
+            }]),
+        };
+        env::log(ownership_log.to_string().as_bytes());
+    }
+
+    // Owner mới xác nhận nhận quyền owner của contract
+    #[payable]
+    pub fn accept_owner(&mut self) {
+        assert_one_yocto();
+        let new_owner_id = env::predecessor_account_id();
+        assert_eq!(
+            Some(&new_owner_id),
+            self.pending_owner_id.as_ref(),
+            "Only pending owner can accept ownership"
+        );
+
+        let old_owner_id = std::mem::replace(&mut self.owner_id, new_owner_id.clone());
+        self.pending_owner_id = None;
+
+        // OWNERSHIP TRANSFERRED LOG
+        let ownership_log: EventLog = EventLog {
+            standard: "zng-market".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OwnershipTransferred(vec![OwnershipTransferLog {
+                old_owner_id: old_owner_id.to_string(),
+                new_owner_id: new_owner_id.to_string(),
+            }]),
+        };
+        env::log(ownership_log.to_string().as_bytes());
+    }
+
+    pub fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+}
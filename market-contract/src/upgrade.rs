@@ -0,0 +1,125 @@
+/**
+ * Upgrade code của contract và migrate state
+ * - Owner gọi `upgrade` với input là code (wasm) mới -> Deploy code mới và gọi `migrate`
+ * - `migrate` đọc state cũ theo version đang lưu và chuyển sang layout hiện tại
+ */
+use crate::*;
+
+// Version của layout state hiện tại
+// Note: Khi thay đổi layout của Contract ở bản release mới -> Tăng STATE_VERSION, freeze layout mới thành ContractVn
+// và thêm variant vào VersionedContract
+pub const STATE_VERSION: u16 = 2;
+
+// Key lưu version của state đang lưu trên chain
+// State được tạo trước khi có versioning sẽ không có key này -> version 1
+pub(crate) const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+const STATE_KEY: &[u8] = b"STATE";
+
+// Gas giữ lại để hoàn thành hàm upgrade sau khi tạo promise gọi migrate
+const GAS_FOR_UPGRADE: Gas = 10_000_000_000_000;
+const NO_DEPOSIT: Balance = 0;
+
+// Layout state của bản deploy đầu tiên (chưa có versioning)
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    pub owner_id: AccountId,
+    pub sales: UnorderedMap<ContractAndTokenId, Sale>,
+    pub by_owner_id: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
+    pub by_contract_id: LookupMap<NFTContractId, UnorderedSet<TokenId>>,
+    pub storage_deposit: LookupMap<AccountId, Balance>,
+}
+
+// Layout state version 2 (thêm pending_owner_id)
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV2 {
+    pub owner_id: AccountId,
+    pub pending_owner_id: Option<AccountId>,
+    pub sales: UnorderedMap<ContractAndTokenId, Sale>,
+    pub by_owner_id: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
+    pub by_contract_id: LookupMap<NFTContractId, UnorderedSet<TokenId>>,
+    pub storage_deposit: LookupMap<AccountId, Balance>,
+}
+
+// Tất cả các layout state mà contract có thể đọc được
+pub enum VersionedContract {
+    V1(ContractV1),
+    V2(ContractV2),
+}
+
+impl From<VersionedContract> for Contract {
+    fn from(state: VersionedContract) -> Self {
+        match state {
+            VersionedContract::V1(state) => Contract {
+                owner_id: state.owner_id,
+                pending_owner_id: None,
+                sales: state.sales,
+                by_owner_id: state.by_owner_id,
+                by_contract_id: state.by_contract_id,
+                storage_deposit: state.storage_deposit,
+            },
+            VersionedContract::V2(state) => Contract {
+                owner_id: state.owner_id,
+                pending_owner_id: state.pending_owner_id,
+                sales: state.sales,
+                by_owner_id: state.by_owner_id,
+                by_contract_id: state.by_contract_id,
+                storage_deposit: state.storage_deposit,
+            },
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    // Input của hàm này là code (wasm) mới, không phải JSON
+    pub fn upgrade(&self) {
+        self.assert_owner();
+
+        let code = env::input().expect("Not found code to deploy");
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                b"migrate".to_vec(),
+                vec![],
+                NO_DEPOSIT,
+                env::prepaid_gas() - env::used_gas() - GAS_FOR_UPGRADE,
+            )
+            .as_return();
+    }
+
+    // Được gọi bởi chính contract sau khi deploy code mới
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let state = env::storage_read(STATE_KEY).expect("Not found state to migrate");
+
+        let versioned_state = match read_state_version() {
+            1 => VersionedContract::V1(
+                ContractV1::try_from_slice(&state).expect("Cannot deserialize state V1"),
+            ),
+            2 => VersionedContract::V2(
+                ContractV2::try_from_slice(&state).expect("Cannot deserialize state V2"),
+            ),
+            version => env::panic(format!("Unknown state version {}", version).as_bytes()),
+        };
+
+        write_state_version();
+        Contract::from(versioned_state)
+    }
+
+    // Version của state mà contract đang chạy
+    pub fn version(&self) -> u16 {
+        read_state_version()
+    }
+}
+
+pub(crate) fn read_state_version() -> u16 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|version| u16::try_from_slice(&version).expect("Cannot deserialize state version"))
+        .unwrap_or(1)
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &STATE_VERSION.try_to_vec().unwrap());
+}
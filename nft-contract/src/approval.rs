@@ -9,7 +9,14 @@ const NO_DEPOSIT: Balance = 0;
 
 pub trait NonFungibleTokenApproval {
     // Cho phép account khác (Marketplace) quyền chuyển token của mình cho người khác
-    fn nft_approve(&mut self, token_id: TokenId, account_id: AccountId, msg: Option<String>);
+    // expires_at: thời điểm approval hết hiệu lực, tính theo block timestamp (nanoseconds). None -> Không hết hạn
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        msg: Option<String>,
+        expires_at: Option<u64>,
+    );
     // Check xem account đã có quyền chuyển Token chưa
     // Nếu approve account_id hợp lệ -> return true, else return false
     fn nft_is_approved(
@@ -47,11 +54,28 @@ impl NonFungibleTokenApproval for Contract {
     // Note: Vì function này sẽ làm tăng data trong Contract -> Thêm payable để user deposit thêm
     // Account ID => market contract id
     #[payable]
-    fn nft_approve(&mut self, token_id: TokenId, account_id: AccountId, msg: Option<String>) {
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        msg: Option<String>,
+        expires_at: Option<u64>,
+    ) {
+        self.assert_approvals_not_paused();
         assert_at_least_one_yocto();
+        if let Some(expires_at) = expires_at {
+            assert!(
+                expires_at > env::block_timestamp(),
+                "Approval expires_at must be in the future"
+            );
+        }
 
         // Kiểm tra xem token có tồn tại hay không
         let mut token = self.tokens_by_id.get(&token_id).expect("Not found token");
+        // Token soulbound không thể approve cho account khác
+        self.assert_not_soulbound(&token_id);
+        // Token đã hết hạn không thể approve cho account khác
+        assert!(!self.internal_is_expired(&token_id), "Token has expired");
 
         // Check xem sender có phải token owner không
         // Chỉ owner mới có quyền approved cho account khác
@@ -65,13 +89,12 @@ impl NonFungibleTokenApproval for Contract {
         let approval_id = token.next_approval_id;
         // Check xem account này đã tồn tại trong list approved_account_ids chưa
         // Add account vào list các tài khoản có thể transfer Token này
-        let is_new_approval = token
+        let old_approval_id = token
             .approved_account_ids
-            .insert(account_id.clone(), approval_id)
-            .is_none();
+            .insert(account_id.clone(), approval_id);
 
         // Nếu approve cho account mới -> Tăng dung lượng data -> tính phí cho user
-        let storage_used = if is_new_approval {
+        let storage_used = if old_approval_id.is_none() {
             bytes_for_approved_account_id(&account_id)
         } else {
             0
@@ -80,8 +103,35 @@ impl NonFungibleTokenApproval for Contract {
         token.next_approval_id += 1;
         self.tokens_by_id.insert(&token_id, &token);
 
+        // Thời hạn của approval cũ không còn tác dụng -> Thay bằng thời hạn mới (nếu có)
+        // Phí lưu trữ thời hạn được tính theo dung lượng thực tế
+        let before_storage_usage = env::storage_usage();
+        self.internal_remove_approval_expirations(&token_id, old_approval_id.iter());
+        if let Some(expires_at) = expires_at {
+            let mut expirations = self.approval_expirations.get(&token_id).unwrap_or_default();
+            expirations.insert(approval_id, expires_at);
+            self.approval_expirations.insert(&token_id, &expirations);
+        }
+
         // Refund nếu user nạp vào thừa phí lưu trữ
-        refund_deposit(storage_used);
+        self.internal_charge_storage_change(
+            before_storage_usage,
+            env::storage_usage() + storage_used,
+        );
+
+        // NFT APPROVE LOG
+        let nft_approve_log: EventLog = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftApprove(vec![NftApproveLog {
+                owner_id: token.owner_id.to_string(),
+                account_id: account_id.to_string(),
+                token_id: token_id.to_string(),
+                approval_id,
+                expires_at,
+            }]),
+        };
+        env::log(nft_approve_log.to_string().as_bytes());
 
         // Nếu có gắn msg -> Thực hiện Cross Contract Call sang market contract
         // msg chứa thông tin: giá, hành động, hàm, ...
@@ -107,18 +157,16 @@ impl NonFungibleTokenApproval for Contract {
         approval_id: Option<u64>,
     ) -> bool {
         let token = self.tokens_by_id.get(&token_id).expect("Token not found");
-        let approval = token.approved_account_ids.get(&approved_account_id);
+        // Approval đã hết hạn được coi như không tồn tại
+        let approval = token
+            .approved_account_ids
+            .get(&approved_account_id)
+            .filter(|approval| !self.internal_is_approval_expired(&token_id, **approval));
 
         // Nếu tồn tại account trong list approved_account_ids -> Check tiếp xem approval_id có đúng ko
-        if let Some(approval) = approval {
-            if approval == &approval_id.unwrap() {
-                true
-            } else {
-                false
-            }
-        } else {
-            false
-        }
+        // Không truyền approval_id -> Chỉ cần account đang được approve
+        approval
+            .is_some_and(|approval| approval_id.is_none_or(|approval_id| *approval == approval_id))
     }
 
     // Note: Khi xoá 1 account khỏi approved_list_ids -> Refund phí lưu trữ data mà user đã trả trước đó
@@ -135,11 +183,26 @@ impl NonFungibleTokenApproval for Contract {
         );
 
         // Nếu xoá quyền thành công
-        if token.approved_account_ids.remove(&account_id).is_some() {
-            // Refund lại số tiền đã deposit để lưu trữ data của user
-            refund_approved_account_ids_iter(sender_id, [account_id].iter());
+        if let Some(approval_id) = token.approved_account_ids.remove(&account_id) {
             // Cập nhật lại danh sách tokens
             self.tokens_by_id.insert(&token_id, &token);
+            // Refund lại số tiền đã deposit để lưu trữ data của user
+            let storage_released =
+                self.internal_remove_approval_expirations(&token_id, [approval_id].iter());
+            refund_storage_released(sender_id.clone(), storage_released);
+            refund_approved_account_ids_iter(sender_id.clone(), [account_id.clone()].iter());
+
+            // NFT REVOKE LOG
+            let nft_revoke_log: EventLog = EventLog {
+                standard: "zng-nft".to_string(),
+                version: "1.0.0".to_string(),
+                event: EventLogVariant::NftRevoke(vec![NftRevokeLog {
+                    owner_id: sender_id.to_string(),
+                    account_id: account_id.to_string(),
+                    token_id: token_id.to_string(),
+                }]),
+            };
+            env::log(nft_revoke_log.to_string().as_bytes());
         }
     }
 
@@ -157,11 +220,251 @@ impl NonFungibleTokenApproval for Contract {
 
         if !token.approved_account_ids.is_empty() {
             // Refund lại số tiền mọi người đã deposit khi gọi hàm revoke_all()
-            refund_approved_account_ids(sender_id, &token.approved_account_ids);
+            let storage_released = self.internal_remove_approval_expirations(
+                &token_id,
+                token.approved_account_ids.values(),
+            );
+            refund_storage_released(sender_id.clone(), storage_released);
+            refund_approved_account_ids(sender_id.clone(), &token.approved_account_ids);
             // Xoá toàn bộ list account đã approved cho token
             token.approved_account_ids.clear();
             // Cập nhật lại danh sách tokens
             self.tokens_by_id.insert(&token_id, &token);
+
+            // NFT REVOKE ALL LOG
+            let nft_revoke_all_log: EventLog = EventLog {
+                standard: "zng-nft".to_string(),
+                version: "1.0.0".to_string(),
+                event: EventLogVariant::NftRevokeAll(vec![NftRevokeAllLog {
+                    owner_id: sender_id.to_string(),
+                    token_id: token_id.to_string(),
+                }]),
+            };
+            env::log(nft_revoke_all_log.to_string().as_bytes());
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    // Thời điểm approval của account_id hết hiệu lực (nanoseconds). None -> Không có approval hoặc không hết hạn
+    pub fn nft_approval_expires_at(&self, token_id: TokenId, account_id: AccountId) -> Option<u64> {
+        let token = self.tokens_by_id.get(&token_id).expect("Not found token");
+        let approval_id = token.approved_account_ids.get(&account_id)?;
+
+        self.approval_expirations
+            .get(&token_id)
+            .and_then(|expirations| expirations.get(approval_id).copied())
+    }
+
+    /**
+     * Xoá các approval đã hết hạn của token, ai cũng có thể gọi
+     * - Refund phí lưu trữ của các approval bị xoá cho owner của token
+     * - Return số lượng approval đã xoá
+     */
+    pub fn nft_clear_expired_approvals(&mut self, token_id: TokenId) -> u64 {
+        let mut token = self.tokens_by_id.get(&token_id).expect("Not found token");
+
+        let expired: Vec<(AccountId, u64)> = token
+            .approved_account_ids
+            .iter()
+            .filter(|(_, approval_id)| self.internal_is_approval_expired(&token_id, **approval_id))
+            .map(|(account_id, approval_id)| (account_id.clone(), *approval_id))
+            .collect();
+        if expired.is_empty() {
+            return 0;
+        }
+
+        for (account_id, _) in expired.iter() {
+            token.approved_account_ids.remove(account_id);
+        }
+        self.tokens_by_id.insert(&token_id, &token);
+
+        let storage_released = self.internal_remove_approval_expirations(
+            &token_id,
+            expired.iter().map(|(_, approval_id)| approval_id),
+        ) + expired
+            .iter()
+            .map(|(account_id, _)| bytes_for_approved_account_id(account_id))
+            .sum::<u64>();
+        refund_storage_released(token.owner_id.clone(), storage_released);
+
+        // NFT REVOKE LOG
+        let nft_revoke_log: EventLog = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftRevoke(
+                expired
+                    .iter()
+                    .map(|(account_id, _)| NftRevokeLog {
+                        owner_id: token.owner_id.to_string(),
+                        account_id: account_id.to_string(),
+                        token_id: token_id.to_string(),
+                    })
+                    .collect(),
+            ),
+        };
+        env::log(nft_revoke_log.to_string().as_bytes());
+
+        expired.len() as u64
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /**
+     * Cho phép operator_id chuyển toàn bộ token của người gọi (tương tự setApprovalForAll)
+     * - Chỉ cần approve 1 lần cho mọi token, kể cả token nhận được sau này
+     * - Operator không còn quyền với token sau khi token được chuyển cho owner khác
+     * - Yêu cầu user nạp tiền để cover phí lưu trữ, refund lại phần thừa
+     */
+    #[payable]
+    pub fn nft_approve_operator(&mut self, operator_id: AccountId) {
+        self.assert_approvals_not_paused();
+        assert_at_least_one_yocto();
+        let owner_id = env::predecessor_account_id();
+        assert_ne!(owner_id, operator_id, "Owner cannot be its own operator");
+
+        let before_storage_usage = env::storage_usage();
+
+        let mut operators = self.operators.get(&owner_id).unwrap_or_else(|| {
+            UnorderedSet::new(
+                StorageKey::OperatorsInnerKey {
+                    account_id_hash: hash_account_id(&owner_id),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        operators.insert(&operator_id);
+        self.operators.insert(&owner_id, &operators);
+
+        self.internal_charge_storage(env::storage_usage() - before_storage_usage);
+
+        // OPERATOR APPROVE LOG
+        let operator_log: EventLog = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OperatorApprove(vec![OperatorLog {
+                owner_id: owner_id.to_string(),
+                operator_id: operator_id.to_string(),
+            }]),
+        };
+        env::log(operator_log.to_string().as_bytes());
+    }
+
+    // Xoá quyền operator của operator_id, refund phí lưu trữ cho owner
+    #[payable]
+    pub fn nft_revoke_operator(&mut self, operator_id: AccountId) {
+        assert_one_yocto();
+        let owner_id = env::predecessor_account_id();
+
+        let mut operators = self.operators.get(&owner_id).expect("Not found operator");
+        let before_storage_usage = env::storage_usage();
+
+        assert!(operators.remove(&operator_id), "Not found operator");
+        if operators.is_empty() {
+            self.operators.remove(&owner_id);
+        } else {
+            self.operators.insert(&owner_id, &operators);
         }
+
+        refund_storage_released(
+            owner_id.clone(),
+            before_storage_usage - env::storage_usage(),
+        );
+
+        // OPERATOR REVOKE LOG
+        let operator_log: EventLog = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OperatorRevoke(vec![OperatorLog {
+                owner_id: owner_id.to_string(),
+                operator_id: operator_id.to_string(),
+            }]),
+        };
+        env::log(operator_log.to_string().as_bytes());
+    }
+
+    // operator_id có quyền chuyển toàn bộ token của owner_id không?
+    pub fn nft_is_operator(&self, owner_id: AccountId, operator_id: AccountId) -> bool {
+        self.internal_is_operator(&owner_id, &operator_id)
+    }
+
+    // Lấy danh sách operators của owner_id (có pagination)
+    pub fn nft_operators(
+        &self,
+        owner_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        let operators = match self.operators.get(&owner_id) {
+            Some(operators) => operators,
+            None => return vec![],
+        };
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        operators
+            .iter()
+            .skip(start as usize) // Pagination
+            .take(limit.unwrap_or(0) as usize) // Pagination
+            .collect()
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_is_operator(
+        &self,
+        owner_id: &AccountId,
+        operator_id: &AccountId,
+    ) -> bool {
+        self.operators
+            .get(owner_id)
+            .is_some_and(|operators| operators.contains(operator_id))
+    }
+
+    // Approval đã hết hạn chưa? Approval không có thời hạn -> Không bao giờ hết hạn
+    pub(crate) fn internal_is_approval_expired(
+        &self,
+        token_id: &TokenId,
+        approval_id: u64,
+    ) -> bool {
+        self.approval_expirations
+            .get(token_id)
+            .and_then(|expirations| expirations.get(&approval_id).copied())
+            .is_some_and(|expires_at| env::block_timestamp() >= expires_at)
+    }
+
+    // Xoá thời hạn của các approval_ids, return số bytes storage được giải phóng
+    // Note: Hàm gọi tự refund phí storage cho account phù hợp
+    pub(crate) fn internal_remove_approval_expirations<'a, I>(
+        &mut self,
+        token_id: &TokenId,
+        approval_ids: I,
+    ) -> u64
+    where
+        I: Iterator<Item = &'a u64>,
+    {
+        let mut expirations = match self.approval_expirations.get(token_id) {
+            Some(expirations) => expirations,
+            None => return 0,
+        };
+
+        let before_storage_usage = env::storage_usage();
+        let before_len = expirations.len();
+        for approval_id in approval_ids {
+            expirations.remove(approval_id);
+        }
+        if expirations.len() == before_len {
+            return 0;
+        }
+
+        if expirations.is_empty() {
+            self.approval_expirations.remove(token_id);
+        } else {
+            self.approval_expirations.insert(token_id, &expirations);
+        }
+
+        before_storage_usage.saturating_sub(env::storage_usage())
     }
 }
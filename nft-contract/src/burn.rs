@@ -0,0 +1,32 @@
+// Hàm burn NFT
+
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    /**
+     * - Chỉ owner của token mới được burn
+     * - Với token soulbound, issuer cũng có quyền burn (thu hồi) token
+     * - Xoá token khỏi tokens_by_id, token_metadata_by_id, tokens_per_owner
+     * - Refund phí storage được giải phóng cho người trả phí lưu trữ của token (mặc định là owner), phí approve cho owner
+     */
+    #[payable]
+    pub fn nft_burn(&mut self, token_id: TokenId, memo: Option<String>) {
+        assert_one_yocto();
+
+        let token = self.tokens_by_id.get(&token_id).expect("Not found token");
+        let predecessor_id = env::predecessor_account_id();
+        if predecessor_id == token.owner_id {
+            self.internal_burn(&token_id, None, memo);
+            return;
+        }
+
+        let issuer_id = self.soulbound_tokens.get(&token_id);
+        assert_eq!(
+            Some(&predecessor_id),
+            issuer_id.as_ref(),
+            "Only owner of the NFT can burn it"
+        );
+        self.internal_burn(&token_id, Some(predecessor_id), memo);
+    }
+}
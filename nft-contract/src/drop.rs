@@ -0,0 +1,434 @@
+/**
+ * Drop launcher: bán token theo 2 phase, allowlist trước rồi tới public
+ * - Mỗi phase có giá riêng (NEAR hoặc FT do owner chỉ định), thời gian mở bán, giới hạn mỗi ví và tổng số lượng
+ * - Metadata của token được lấy ngẫu nhiên từ mint pool (xem pool.rs)
+ * - Tiền bán được giữ trong treasury của contract, owner có thể rút bất cứ lúc nào
+ * - Mua bằng NEAR: gọi drop_mint. Mua bằng FT: gọi ft_transfer_call tới contract này với msg = DropMintArgs
+ */
+use crate::*;
+
+const GAS_FOR_FT_TRANSFER: Gas = 15_000_000_000_000;
+const GAS_FOR_RESOLVE_WITHDRAW: Gas = 10_000_000_000_000;
+const NO_DEPOSIT: Balance = 0;
+
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum DropPhaseKind {
+    Allowlist,
+    Public,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropPhase {
+    pub price: U128,                       // Giá của 1 token
+    pub ft_contract_id: Option<AccountId>, // FT dùng để thanh toán, None -> Thanh toán bằng NEAR
+    pub starts_at: u64,                    // Thời điểm mở bán (nanoseconds)
+    pub ends_at: u64,                      // Thời điểm kết thúc (nanoseconds)
+    pub max_per_wallet: Option<u64>, // Số token tối đa mỗi ví được mua trong phase (None -> Không giới hạn)
+    pub max_supply: Option<u64>, // Tổng số token tối đa bán trong phase (None -> Đến khi hết pool)
+    #[serde(default)]
+    pub minted: u64, // Số token đã bán trong phase, contract tự cập nhật
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropConfig {
+    pub allowlist: Option<DropPhase>,
+    pub public: Option<DropPhase>,
+}
+
+// Số token mỗi ví đã mua trong từng phase
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct DropWalletMinted {
+    pub allowlist: u64,
+    pub public: u64,
+}
+
+// msg của ft_transfer_call khi mua bằng FT
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropMintArgs {
+    pub quantity: u64,
+}
+
+pub trait FungibleTokenReceiver {
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128>;
+}
+
+#[ext_contract(ext_ft_contract)]
+trait FungibleTokenCore {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+#[ext_contract(ext_drop)]
+trait DropResolver {
+    fn drop_resolve_withdraw(&mut self, ft_contract_id: AccountId, amount: U128);
+}
+
+#[near_bindgen]
+impl Contract {
+    /**
+     * Cấu hình drop (chỉ owner của contract)
+     * - Phase allowlist phải kết thúc trước khi phase public bắt đầu
+     * - Số token đã bán của từng phase được giữ nguyên khi cập nhật cấu hình
+     */
+    #[payable]
+    pub fn set_drop(&mut self, allowlist: Option<DropPhase>, public: Option<DropPhase>) {
+        assert_one_yocto();
+        self.assert_owner();
+
+        for phase in allowlist.iter().chain(public.iter()) {
+            assert!(
+                phase.starts_at < phase.ends_at,
+                "Drop phase must start before it ends"
+            );
+        }
+        if let (Some(allowlist), Some(public)) = (&allowlist, &public) {
+            assert!(
+                allowlist.ends_at <= public.starts_at,
+                "Allowlist phase must end before public phase starts"
+            );
+        }
+
+        let previous = self.drop_config.take();
+        let with_minted = |phase: Option<DropPhase>, previous: Option<&DropPhase>| {
+            phase.map(|mut phase| {
+                phase.minted = previous.map(|previous| previous.minted).unwrap_or(0);
+                phase
+            })
+        };
+        self.drop_config = Some(DropConfig {
+            allowlist: with_minted(
+                allowlist,
+                previous
+                    .as_ref()
+                    .and_then(|config| config.allowlist.as_ref()),
+            ),
+            public: with_minted(
+                public,
+                previous.as_ref().and_then(|config| config.public.as_ref()),
+            ),
+        });
+    }
+
+    pub fn get_drop(&self) -> Option<DropConfig> {
+        self.drop_config.clone()
+    }
+
+    // Phase đang mở bán tại thời điểm hiện tại
+    pub fn get_drop_active_phase(&self) -> Option<DropPhaseKind> {
+        self.internal_active_drop_phase().map(|(kind, _)| kind)
+    }
+
+    // Thêm các account vào allowlist (chỉ owner của contract), owner trả phí lưu trữ
+    #[payable]
+    pub fn drop_add_to_allowlist(&mut self, account_ids: Vec<AccountId>) {
+        assert_at_least_one_yocto();
+        self.assert_owner();
+
+        let before_storage_usage = env::storage_usage();
+
+        for account_id in account_ids.iter() {
+            self.drop_allowlist.insert(account_id);
+        }
+
+        self.internal_charge_storage(env::storage_usage() - before_storage_usage);
+    }
+
+    // Xoá các account khỏi allowlist, refund phí lưu trữ cho owner
+    #[payable]
+    pub fn drop_remove_from_allowlist(&mut self, account_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_owner();
+
+        let before_storage_usage = env::storage_usage();
+
+        for account_id in account_ids.iter() {
+            self.drop_allowlist.remove(account_id);
+        }
+
+        refund_storage_released(
+            env::predecessor_account_id(),
+            before_storage_usage - env::storage_usage(),
+        );
+    }
+
+    pub fn drop_is_allowlisted(&self, account_id: AccountId) -> bool {
+        self.drop_allowlist.contains(&account_id)
+    }
+
+    // Số token account_id đã mua trong phase
+    pub fn drop_minted_by(&self, account_id: AccountId, phase: DropPhaseKind) -> u64 {
+        self.drop_wallet_minted
+            .get(&account_id)
+            .map(|minted| minted.get(phase))
+            .unwrap_or(0)
+    }
+
+    /**
+     * Mua quantity token bằng NEAR trong phase đang mở bán
+     * - Deposit = giá * quantity + phí lưu trữ (phí lưu trữ có thể trừ vào số dư storage đã nạp)
+     * - Refund lại NEAR user deposit thừa
+     * - Return token_ids của các token vừa mint
+     */
+    #[payable]
+    pub fn drop_mint(&mut self, quantity: u64) -> Vec<TokenId> {
+        let buyer_id = env::predecessor_account_id();
+
+        let (token_ids, total_price, storage_usage) =
+            self.internal_drop_mint(&buyer_id, quantity, None);
+
+        let attached_deposit = env::attached_deposit();
+        assert!(
+            attached_deposit >= total_price,
+            "Must attach at least {} yoctoNear to pay for the drop",
+            total_price
+        );
+        self.drop_treasury_near += total_price;
+
+        self.internal_charge_storage_from(&buyer_id, attached_deposit - total_price, storage_usage);
+
+        token_ids
+    }
+
+    // Số NEAR (ft_contract_id = None) hoặc FT đang có trong treasury
+    pub fn get_drop_treasury(&self, ft_contract_id: Option<AccountId>) -> U128 {
+        U128(match ft_contract_id {
+            Some(ft_contract_id) => self.drop_treasury_ft.get(&ft_contract_id).unwrap_or(0),
+            None => self.drop_treasury_near,
+        })
+    }
+
+    /**
+     * Rút tiền từ treasury (chỉ owner của contract)
+     * - ft_contract_id = None -> Rút NEAR, ngược lại rút FT
+     * - amount = None -> Rút toàn bộ
+     * - receiver_id = None -> Chuyển cho owner
+     * - Nếu chuyển FT thất bại -> Số FT được cộng lại vào treasury
+     */
+    #[payable]
+    pub fn drop_withdraw(
+        &mut self,
+        ft_contract_id: Option<AccountId>,
+        amount: Option<U128>,
+        receiver_id: Option<AccountId>,
+    ) -> Promise {
+        assert_one_yocto();
+        self.assert_owner();
+        let receiver_id = receiver_id.unwrap_or_else(|| self.owner_id.clone());
+
+        let available = self.get_drop_treasury(ft_contract_id.clone()).0;
+        let amount = amount.map(|amount| amount.0).unwrap_or(available);
+        assert!(amount > 0, "Nothing to withdraw");
+        assert!(
+            amount <= available,
+            "Treasury only has {} to withdraw",
+            available
+        );
+
+        match ft_contract_id {
+            Some(ft_contract_id) => {
+                self.drop_treasury_ft
+                    .insert(&ft_contract_id, &(available - amount));
+
+                ext_ft_contract::ft_transfer(
+                    receiver_id,
+                    U128(amount),
+                    None,
+                    &ft_contract_id,
+                    1,
+                    GAS_FOR_FT_TRANSFER,
+                )
+                .then(ext_drop::drop_resolve_withdraw(
+                    ft_contract_id,
+                    U128(amount),
+                    &env::current_account_id(),
+                    NO_DEPOSIT,
+                    GAS_FOR_RESOLVE_WITHDRAW,
+                ))
+            }
+            None => {
+                self.drop_treasury_near = available - amount;
+                Promise::new(receiver_id).transfer(amount)
+            }
+        }
+    }
+
+    // Chuyển FT cho owner thất bại -> Cộng lại vào treasury
+    #[private]
+    pub fn drop_resolve_withdraw(&mut self, ft_contract_id: AccountId, amount: U128) {
+        if let PromiseResult::Failed = env::promise_result(0) {
+            let available = self.drop_treasury_ft.get(&ft_contract_id).unwrap_or(0);
+            self.drop_treasury_ft
+                .insert(&ft_contract_id, &(available + amount.0));
+        }
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for Contract {
+    /**
+     * Mua token bằng FT qua ft_transfer_call, msg = DropMintArgs
+     * - Người mua phải nạp trước storage (storage_deposit) để trả phí lưu trữ của token
+     * - Return số FT thừa để FT contract refund lại cho người mua
+     */
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        let ft_contract_id = env::predecessor_account_id();
+        let DropMintArgs { quantity } =
+            near_sdk::serde_json::from_str(&msg).expect("Not valid drop mint args");
+
+        let (_, total_price, storage_usage) =
+            self.internal_drop_mint(&sender_id, quantity, Some(&ft_contract_id));
+        assert!(
+            amount.0 >= total_price,
+            "Must transfer at least {} to pay for the drop",
+            total_price
+        );
+        let available = self.drop_treasury_ft.get(&ft_contract_id).unwrap_or(0);
+        self.drop_treasury_ft
+            .insert(&ft_contract_id, &(available + total_price));
+
+        self.internal_charge_storage_from(&sender_id, 0, storage_usage);
+
+        PromiseOrValue::Value(U128(amount.0 - total_price))
+    }
+}
+
+impl DropWalletMinted {
+    fn get(&self, phase: DropPhaseKind) -> u64 {
+        match phase {
+            DropPhaseKind::Allowlist => self.allowlist,
+            DropPhaseKind::Public => self.public,
+        }
+    }
+
+    fn get_mut(&mut self, phase: DropPhaseKind) -> &mut u64 {
+        match phase {
+            DropPhaseKind::Allowlist => &mut self.allowlist,
+            DropPhaseKind::Public => &mut self.public,
+        }
+    }
+}
+
+impl Contract {
+    // Phase đang mở bán (starts_at <= now < ends_at)
+    fn internal_active_drop_phase(&self) -> Option<(DropPhaseKind, DropPhase)> {
+        let config = self.drop_config.clone()?;
+        let now = env::block_timestamp();
+        let is_active = |phase: &DropPhase| phase.starts_at <= now && now < phase.ends_at;
+
+        config
+            .allowlist
+            .filter(is_active)
+            .map(|phase| (DropPhaseKind::Allowlist, phase))
+            .or_else(|| {
+                config
+                    .public
+                    .filter(is_active)
+                    .map(|phase| (DropPhaseKind::Public, phase))
+            })
+    }
+
+    /**
+     * Mint quantity token từ mint pool cho buyer_id trong phase đang mở bán
+     * - Kiểm tra đồng tiền thanh toán, allowlist, giới hạn mỗi ví và tổng số lượng của phase
+     * - Không thu tiền và phí lưu trữ -> Hàm gọi sẽ tự xử lý
+     * - Return token_ids, tổng số tiền phải trả và số bytes storage người mua phải trả phí
+     *   (các token vừa mint và số lượng đã mua của ví, không tính pool đã refund cho owner)
+     */
+    fn internal_drop_mint(
+        &mut self,
+        buyer_id: &AccountId,
+        quantity: u64,
+        ft_contract_id: Option<&AccountId>,
+    ) -> (Vec<TokenId>, Balance, u64) {
+        self.assert_mint_not_paused();
+        assert!(quantity > 0, "Quantity must be greater than 0");
+
+        let (kind, mut phase) = self
+            .internal_active_drop_phase()
+            .expect("No active drop phase");
+        assert!(
+            phase.ft_contract_id.as_ref() == ft_contract_id,
+            "Drop phase must be paid in {}",
+            phase.ft_contract_id.as_deref().unwrap_or("NEAR")
+        );
+        if kind == DropPhaseKind::Allowlist {
+            assert!(
+                self.drop_allowlist.contains(buyer_id),
+                "Account is not in the drop allowlist"
+            );
+        }
+
+        let mut wallet_minted = self.drop_wallet_minted.get(buyer_id).unwrap_or_default();
+        let minted_by_wallet = wallet_minted.get_mut(kind);
+        *minted_by_wallet += quantity;
+        if let Some(max_per_wallet) = phase.max_per_wallet {
+            assert!(
+                *minted_by_wallet <= max_per_wallet,
+                "Exceeded wallet limit of {} tokens",
+                max_per_wallet
+            );
+        }
+        phase.minted += quantity;
+        if let Some(max_supply) = phase.max_supply {
+            assert!(
+                phase.minted <= max_supply,
+                "Exceeded phase supply of {} tokens",
+                max_supply
+            );
+        }
+        let before_storage_usage = env::storage_usage();
+        self.drop_wallet_minted.insert(buyer_id, &wallet_minted);
+        let mut storage_usage = env::storage_usage() - before_storage_usage;
+
+        let mut config = self.drop_config.take().unwrap();
+        match kind {
+            DropPhaseKind::Allowlist => config.allowlist = Some(phase.clone()),
+            DropPhaseKind::Public => config.public = Some(phase.clone()),
+        }
+        self.drop_config = Some(config);
+
+        let token_ids: Vec<TokenId> = (0..quantity)
+            .map(|_| {
+                let (token_id, token_storage_usage) = self.internal_mint_random(buyer_id, buyer_id);
+                storage_usage += token_storage_usage;
+                token_id
+            })
+            .collect();
+
+        // NFT MINT LOG
+        let nft_mint_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftMint(vec![NftMintLog {
+                owner_id: buyer_id.to_string(),
+                token_ids: token_ids.clone(),
+                memo: None,
+            }]),
+        };
+        env::log(nft_mint_log.to_string().as_bytes());
+
+        let total_price = phase
+            .price
+            .0
+            .checked_mul(Balance::from(quantity))
+            .expect("Total price overflow");
+
+        (token_ids, total_price, storage_usage)
+    }
+}
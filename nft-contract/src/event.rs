@@ -5,13 +5,16 @@
  * VD: NEAR Wallet có thể gọi thêm các methods `nft_metadata`
  * và `nft_tokens_for_owner` để hiển thị NFT chính xác
  */
+use std::collections::HashMap;
 use std::fmt;
 
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::serde_json;
 
 /// Enum that represents the data type of the EventLog.
-/// The enum can either be an NftMint or an NftTransfer.
+/// The enum can either be an NftMint, an NftTransfer, an NftBurn, a metadata update,
+/// a reveal, an approval change (approve, revoke, operator) or one of the contract admin events
+/// (ownership transfer, pause, minters, royalty cap).
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "event", content = "data")]
 #[serde(rename_all = "snake_case")]
@@ -20,6 +23,23 @@ use near_sdk::serde_json;
 pub enum EventLogVariant {
     NftMint(Vec<NftMintLog>),
     NftTransfer(Vec<NftTransferLog>),
+    NftBurn(Vec<NftBurnLog>),
+    NftMetadataUpdate(Vec<NftMetadataUpdateLog>),
+    ContractMetadataUpdate(Vec<ContractMetadataUpdateLog>),
+    OwnershipTransferStarted(Vec<OwnershipTransferLog>),
+    OwnershipTransferred(Vec<OwnershipTransferLog>),
+    ContractPaused(Vec<PauseLog>),
+    ContractUnpaused(Vec<PauseLog>),
+    NftApprove(Vec<NftApproveLog>),
+    NftRevoke(Vec<NftRevokeLog>),
+    NftRevokeAll(Vec<NftRevokeAllLog>),
+    OperatorApprove(Vec<OperatorLog>),
+    OperatorRevoke(Vec<OperatorLog>),
+    MinterAdd(Vec<MinterLog>),
+    MinterRemove(Vec<MinterLog>),
+    MaxRoyaltyUpdate(Vec<MaxRoyaltyUpdateLog>),
+    DefaultRoyaltyUpdate(Vec<DefaultRoyaltyUpdateLog>),
+    NftReveal(Vec<NftRevealLog>),
 }
 
 /// Interface to capture data about an event
@@ -86,6 +106,181 @@ pub struct NftTransferLog {
     pub memo: Option<String>,
 }
 
+/// An event log to capture token burning
+///
+/// Arguments
+/// * `owner_id`: owner of tokens to burn
+/// * `authorized_id`: approved account to burn, if applicable
+/// * `token_ids`: ["1","2"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurnLog {
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture token metadata update (nep171 version 1.1.0)
+///
+/// Arguments
+/// * `token_ids`: ["1", "abc"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftMetadataUpdateLog {
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture contract metadata update (nep171 version 1.1.0)
+///
+/// Arguments
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractMetadataUpdateLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture contract ownership transfer
+///
+/// Arguments
+/// * `old_owner_id`: current owner of the contract
+/// * `new_owner_id`: proposed (or accepted) owner of the contract
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnershipTransferLog {
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+}
+
+/// An event log to capture contract pause / unpause
+///
+/// Arguments
+/// * `features`: ["mint", "transfers", "approvals"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseLog {
+    pub features: Vec<String>,
+}
+
+/// An event log to capture token approval
+///
+/// Arguments
+/// * `owner_id`: owner of the token
+/// * `account_id`: approved account (ex. market contract)
+/// * `token_id`: "1"
+/// * `approval_id`: id of the new approval
+/// * `expires_at`: optional approval expiry, block timestamp in nanoseconds
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftApproveLog {
+    pub owner_id: String,
+    pub account_id: String,
+    pub token_id: String,
+    pub approval_id: u64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+/// An event log to capture token approval revocation
+///
+/// Arguments
+/// * `owner_id`: owner of the token
+/// * `account_id`: revoked account
+/// * `token_id`: "1"
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftRevokeLog {
+    pub owner_id: String,
+    pub account_id: String,
+    pub token_id: String,
+}
+
+/// An event log to capture revocation of all approvals of a token
+///
+/// Arguments
+/// * `owner_id`: owner of the token
+/// * `token_id`: "1"
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftRevokeAllLog {
+    pub owner_id: String,
+    pub token_id: String,
+}
+
+/// An event log to capture operator approval / revocation
+///
+/// Arguments
+/// * `owner_id`: owner of the tokens
+/// * `operator_id`: account allowed to transfer all tokens of the owner
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorLog {
+    pub owner_id: String,
+    pub operator_id: String,
+}
+
+/// An event log to capture minter authorization changes
+///
+/// Arguments
+/// * `account_id`: minter account
+/// * `quota`: optional maximum number of tokens to mint
+/// * `expires_at`: optional authorization expiry, block timestamp in nanoseconds
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MinterLog {
+    pub account_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+/// An event log to capture royalty cap update
+///
+/// Arguments
+/// * `old_max_royalty`: previous cap in basis points
+/// * `new_max_royalty`: new cap in basis points
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MaxRoyaltyUpdateLog {
+    pub old_max_royalty: u32,
+    pub new_max_royalty: u32,
+}
+
+/// An event log to capture default royalty update
+///
+/// Arguments
+/// * `royalty`: new default royalty in basis points, e.g. {"artist.near": 1000}
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DefaultRoyaltyUpdateLog {
+    pub royalty: HashMap<String, u32>,
+}
+
+/// An event log to capture a batch of revealed token metadata
+///
+/// Arguments
+/// * `token_ids`: ["1", "abc"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftRevealLog {
+    pub token_ids: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +307,124 @@ mod tests {
         assert_eq!(expected, log.to_string());
     }
 
+    #[test]
+    fn zng_format_approve() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"nft_approve","data":[{"owner_id":"user1.near","account_id":"market.near","token_id":"token","approval_id":3,"expires_at":1000}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftApprove(vec![NftApproveLog {
+                owner_id: "user1.near".to_string(),
+                account_id: "market.near".to_string(),
+                token_id: "token".to_string(),
+                approval_id: 3,
+                expires_at: Some(1000),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn zng_format_revoke() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"nft_revoke","data":[{"owner_id":"user1.near","account_id":"market.near","token_id":"token"}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftRevoke(vec![NftRevokeLog {
+                owner_id: "user1.near".to_string(),
+                account_id: "market.near".to_string(),
+                token_id: "token".to_string(),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn zng_format_revoke_all() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"nft_revoke_all","data":[{"owner_id":"user1.near","token_id":"token"}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftRevokeAll(vec![NftRevokeAllLog {
+                owner_id: "user1.near".to_string(),
+                token_id: "token".to_string(),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn zng_format_operator_approve() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"operator_approve","data":[{"owner_id":"user1.near","operator_id":"market.near"}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OperatorApprove(vec![OperatorLog {
+                owner_id: "user1.near".to_string(),
+                operator_id: "market.near".to_string(),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn zng_format_minter_add() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"minter_add","data":[{"account_id":"minter.near","quota":10}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::MinterAdd(vec![MinterLog {
+                account_id: "minter.near".to_string(),
+                quota: Some(10),
+                expires_at: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn zng_format_max_royalty_update() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"max_royalty_update","data":[{"old_max_royalty":5000,"new_max_royalty":2500}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::MaxRoyaltyUpdate(vec![MaxRoyaltyUpdateLog {
+                old_max_royalty: 5000,
+                new_max_royalty: 2500,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn zng_format_default_royalty_update() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"default_royalty_update","data":[{"royalty":{"artist.near":1000}}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DefaultRoyaltyUpdate(vec![DefaultRoyaltyUpdateLog {
+                royalty: [("artist.near".to_string(), 1000)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn zng_format_reveal() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"nft_reveal","data":[{"token_ids":["1","2"]}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftReveal(vec![NftRevealLog {
+                token_ids: vec!["1".to_string(), "2".to_string()],
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
     #[test]
     fn nep_format_mint() {
         let expected = r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[{"owner_id":"foundation.near","token_ids":["aurora","proximitylabs"]}]}"#;
@@ -143,4 +456,74 @@ mod tests {
         };
         assert_eq!(expected, log.to_string());
     }
+
+    #[test]
+    fn nep_format_burn() {
+        let expected = r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_burn","data":[{"owner_id":"foundation.near","token_ids":["aurora","proximitylabs"]}]}"#;
+        let log = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: "foundation.near".to_owned(),
+                token_ids: vec!["aurora".to_string(), "proximitylabs".to_string()],
+                authorized_id: None,
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn nep_format_metadata_update() {
+        let expected = r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"nft_metadata_update","data":[{"token_ids":["aurora","proximitylabs"]}]}"#;
+        let log = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.1.0".to_string(),
+            event: EventLogVariant::NftMetadataUpdate(vec![NftMetadataUpdateLog {
+                token_ids: vec!["aurora".to_string(), "proximitylabs".to_string()],
+                memo: None,
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn nep_format_contract_metadata_update() {
+        let expected = r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"contract_metadata_update","data":[{"memo":"Update icon"}]}"#;
+        let log = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.1.0".to_string(),
+            event: EventLogVariant::ContractMetadataUpdate(vec![ContractMetadataUpdateLog {
+                memo: Some("Update icon".to_string()),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn zng_format_ownership_transferred() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"ownership_transferred","data":[{"old_owner_id":"user1.near","new_owner_id":"user2.near"}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::OwnershipTransferred(vec![OwnershipTransferLog {
+                old_owner_id: "user1.near".to_string(),
+                new_owner_id: "user2.near".to_string(),
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
+
+    #[test]
+    fn zng_format_contract_paused() {
+        let expected = r#"EVENT_JSON:{"standard":"zng-nft","version":"1.0.0","event":"contract_paused","data":[{"features":["mint","transfers"]}]}"#;
+        let log = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ContractPaused(vec![PauseLog {
+                features: vec!["mint".to_string(), "transfers".to_string()],
+            }]),
+        };
+        assert_eq!(expected, log.to_string());
+    }
 }
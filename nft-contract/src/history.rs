@@ -0,0 +1,148 @@
+/**
+ * Lịch sử sở hữu (provenance) của từng token, lưu on-chain để xem mà không cần indexer
+ * - Ghi lại mint, mỗi lần transfer (kèm authorized_id, memo) và giá bán nếu transfer qua nft_transfer_payout
+ * - Mỗi token chỉ giữ tối đa MAX_TOKEN_HISTORY entry gần nhất, entry cũ nhất bị xoá khi đầy
+ * - Phí lưu trữ entry do người thực hiện hành động trả (người mint, người transfer)
+ *   Transfer chỉ đính kèm 1 yoctoNEAR -> Phí được trừ vào số dư storage đã nạp trước (storage_deposit)
+ * - Entry cũ bị xoá khi đầy hoặc khi token bị burn không được refund (không biết chắc ai đã trả)
+ * - Memo được cắt ngắn còn MAX_HISTORY_MEMO_LENGTH bytes để giới hạn phí lưu trữ của mỗi entry
+ */
+use crate::*;
+
+// Số entry tối đa được lưu cho mỗi token
+pub const MAX_TOKEN_HISTORY: usize = 20;
+// Độ dài tối đa của memo được lưu trong lịch sử
+pub const MAX_HISTORY_MEMO_LENGTH: usize = 128;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum TokenHistoryKind {
+    Mint,
+    Transfer,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenHistoryEntry {
+    pub kind: TokenHistoryKind,
+    pub old_owner_id: Option<AccountId>, // None khi mint
+    pub new_owner_id: AccountId,
+    pub authorized_id: Option<AccountId>, // Account được approve (hoặc operator) thực hiện transfer thay owner
+    pub memo: Option<String>,
+    pub price: Option<U128>, // Giá bán, chỉ có khi transfer qua nft_transfer_payout
+    pub timestamp: u64,      // Unix epoch in milliseconds
+}
+
+#[near_bindgen]
+impl Contract {
+    // Lấy lịch sử của token, từ entry cũ nhất tới mới nhất (có pagination)
+    pub fn nft_token_history(
+        &self,
+        token_id: TokenId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<TokenHistoryEntry> {
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        self.token_history
+            .get(&token_id)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(start as usize) // Pagination
+            .take(limit.unwrap_or(0) as usize) // Pagination
+            .collect()
+    }
+}
+
+impl Contract {
+    // Thêm entry vào lịch sử của token, xoá entry cũ nhất nếu đã đầy
+    // Không tính phí storage -> Hàm gọi sẽ tự xử lý
+    // Return số bytes storage của entry mới (không trừ phần của entry cũ bị xoá)
+    pub(crate) fn internal_push_history(
+        &mut self,
+        token_id: &TokenId,
+        entry: TokenHistoryEntry,
+    ) -> u64 {
+        let before_storage_usage = env::storage_usage();
+
+        let mut history = self.token_history.get(token_id).unwrap_or_default();
+        let mut evicted_storage = 0;
+        if history.len() >= MAX_TOKEN_HISTORY {
+            evicted_storage = history.remove(0).try_to_vec().unwrap().len() as u64;
+        }
+        history.push(entry);
+        self.token_history.insert(token_id, &history);
+
+        env::storage_usage() + evicted_storage - before_storage_usage
+    }
+
+    // Ghi lại 1 lần transfer, phí lưu trữ do người gọi (predecessor) trả
+    // authorized_id là người gọi nếu không phải owner cũ
+    pub(crate) fn internal_record_transfer(
+        &mut self,
+        token_id: &TokenId,
+        old_owner_id: &AccountId,
+        new_owner_id: &AccountId,
+        memo: Option<String>,
+        price: Option<U128>,
+    ) {
+        let sender_id = env::predecessor_account_id();
+        let authorized_id = if &sender_id != old_owner_id {
+            Some(sender_id)
+        } else {
+            None
+        };
+        let entry_storage = self.internal_push_history(
+            token_id,
+            TokenHistoryEntry {
+                kind: TokenHistoryKind::Transfer,
+                old_owner_id: Some(old_owner_id.clone()),
+                new_owner_id: new_owner_id.clone(),
+                authorized_id,
+                memo: memo.map(truncate_history_memo),
+                price,
+                timestamp: env::block_timestamp() / 1_000_000,
+            },
+        );
+
+        self.internal_charge_storage(entry_storage);
+    }
+
+    // Huỷ entry transfer cuối cùng khi transfer bị rollback (nft_resolve_transfer)
+    // Return số bytes storage được giải phóng
+    pub(crate) fn internal_pop_transfer_history(
+        &mut self,
+        token_id: &TokenId,
+        new_owner_id: &AccountId,
+    ) -> u64 {
+        let mut history = match self.token_history.get(token_id) {
+            Some(history) => history,
+            None => return 0,
+        };
+        match history.last() {
+            Some(entry)
+                if entry.kind == TokenHistoryKind::Transfer
+                    && &entry.new_owner_id == new_owner_id => {}
+            _ => return 0,
+        }
+
+        let before_storage_usage = env::storage_usage();
+        history.pop();
+        self.token_history.insert(token_id, &history);
+
+        before_storage_usage - env::storage_usage()
+    }
+}
+
+// Cắt memo còn tối đa MAX_HISTORY_MEMO_LENGTH bytes (không cắt giữa 1 ký tự UTF-8)
+fn truncate_history_memo(mut memo: String) -> String {
+    if memo.len() > MAX_HISTORY_MEMO_LENGTH {
+        let mut end = MAX_HISTORY_MEMO_LENGTH;
+        while !memo.is_char_boundary(end) {
+            end -= 1;
+        }
+        memo.truncate(end);
+    }
+    memo
+}
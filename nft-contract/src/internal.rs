@@ -2,6 +2,15 @@ use crate::*;
 
 #[near_bindgen]
 impl Contract {
+    // Chỉ owner của contract mới được gọi các hàm quản trị
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only contract owner can call this method"
+        );
+    }
+
     // Thêm 1 token vào danh sách sở hữu bởi owner
     pub(crate) fn internal_add_token_to_owner(
         &mut self,
@@ -49,6 +58,201 @@ impl Contract {
         }
     }
 
+    // Lấy thông tin minter của account_id, báo lỗi nếu account không có quyền mint hoặc đã hết hạn
+    fn internal_get_active_minter(&self, account_id: &AccountId) -> Minter {
+        let minter = self
+            .minters
+            .get(account_id)
+            .expect("Only authorized minters can mint");
+
+        if let Some(expires_at) = minter.expires_at {
+            assert!(
+                env::block_timestamp() < expires_at,
+                "Minter authorization has expired"
+            );
+        }
+
+        minter
+    }
+
+    // Kiểm tra account_id có quyền mint không (không trừ quota)
+    pub(crate) fn internal_assert_minter(&self, account_id: &AccountId) {
+        // Owner của contract luôn được phép mint
+        if account_id != &self.owner_id {
+            self.internal_get_active_minter(account_id);
+        }
+    }
+
+    // Kiểm tra quyền mint và trừ quota của minter khi mint `amount` token
+    pub(crate) fn internal_use_mint_quota(&mut self, account_id: &AccountId, amount: u64) {
+        // Owner của contract luôn được phép mint, không giới hạn quota
+        if account_id == &self.owner_id {
+            return;
+        }
+
+        let mut minter = self.internal_get_active_minter(account_id);
+        if let Some(quota) = minter.quota {
+            assert!(
+                minter.minted + amount <= quota,
+                "Minter quota exceeded, remaining {}",
+                quota.saturating_sub(minter.minted)
+            );
+        }
+
+        minter.minted += amount;
+        self.minters.insert(account_id, &minter);
+    }
+
+    // Lấy metadata của token
+    // Nếu token là edition của series -> Tạo metadata từ metadata của series
+    pub(crate) fn internal_token_metadata(&self, token_id: &TokenId) -> TokenMetadata {
+        let metadata = self.token_metadata_by_id.get(token_id).unwrap();
+
+        let (series, edition) = match parse_edition_token_id(token_id)
+            .and_then(|(series_id, edition)| Some((self.series_by_id.get(&series_id)?, edition)))
+        {
+            Some(series_and_edition) => series_and_edition,
+            None => return metadata,
+        };
+
+        TokenMetadata {
+            title: series
+                .metadata
+                .title
+                .map(|title| format!("{} #{}", title, edition)),
+            issued_at: metadata.issued_at,
+            ..series.metadata
+        }
+    }
+
+    // Sinh token_id tiếp theo dạng `<prefix><số thứ tự>`, bỏ qua các token_id đã được mint thủ công
+    pub(crate) fn internal_next_token_id(&mut self) -> TokenId {
+        loop {
+            let token_id = format!("{}{}", self.token_id_prefix, self.next_token_id);
+            self.next_token_id += 1;
+            if !self.tokens_by_id.contains_key(&token_id) {
+                return token_id;
+            }
+        }
+    }
+
+    // Tạo token mới cho receiver_id
+    // Nếu có soulbound_issuer -> Token không thể transfer, chỉ owner hoặc issuer mới có thể burn
+    // Các khoản refund phí lưu trữ của token sẽ trả về cho storage_payer (ex. relayer mint hộ, người tự mint)
+    // Không tính phí storage và không log event -> Hàm gọi sẽ tự xử lý
+    // Return số bytes storage của token (kể cả entry mint trong lịch sử) để hàm gọi tính phí
+    pub(crate) fn internal_mint(
+        &mut self,
+        token_id: &TokenId,
+        metadata: &TokenMetadata,
+        receiver_id: &AccountId,
+        royalty: HashMap<AccountId, u32>,
+        soulbound_issuer: Option<&AccountId>,
+        storage_payer: &AccountId,
+    ) -> u64 {
+        metadata.assert_valid();
+
+        let before_storage_usage = env::storage_usage();
+
+        let token = Token {
+            owner_id: receiver_id.clone(),
+            approved_account_ids: HashMap::default(),
+            next_approval_id: 0,
+            royalty,
+        };
+
+        // Nếu token_id đã tồn tại trong list tokens_by_id thì báo lỗi
+        // Trong LookupMap, nếu key chưa tồn tại trong map -> Hàm insert return None
+        assert!(
+            self.tokens_by_id.insert(token_id, &token).is_none(),
+            "Token already exists"
+        );
+
+        // Thêm token metadata
+        self.token_metadata_by_id.insert(token_id, metadata);
+
+        // Thêm token vào danh sách sở hữu bởi owner
+        self.internal_add_token_to_owner(token_id, receiver_id);
+
+        if let Some(issuer_id) = soulbound_issuer {
+            self.soulbound_tokens.insert(token_id, issuer_id);
+        }
+
+        // Luôn lưu người trả phí -> Deposit của người tự mint không đi theo token khi token được transfer
+        self.token_storage_payers.insert(token_id, storage_payer);
+
+        // Lịch sử của token bắt đầu từ lần mint
+        self.internal_push_history(
+            token_id,
+            TokenHistoryEntry {
+                kind: TokenHistoryKind::Mint,
+                old_owner_id: None,
+                new_owner_id: receiver_id.clone(),
+                authorized_id: None,
+                memo: None,
+                price: None,
+                timestamp: env::block_timestamp() / 1_000_000,
+            },
+        );
+
+        env::storage_usage() - before_storage_usage
+    }
+
+    // Account nhận các khoản refund phí lưu trữ của token: người trả phí khi mint
+    // Token được mint trước khi lưu người trả phí -> Mặc định là owner_id
+    pub(crate) fn internal_storage_payer(
+        &self,
+        token_id: &TokenId,
+        owner_id: &AccountId,
+    ) -> AccountId {
+        self.token_storage_payers
+            .get(token_id)
+            .unwrap_or_else(|| owner_id.clone())
+    }
+
+    /**
+     * Ghi metadata mới của token do owner của contract cập nhật, người gọi trả phí phần tăng thêm
+     * - Phần metadata lớn hơn lúc mint được lưu trong token_metadata_growth
+     * - Metadata nhỏ lại: trừ vào phần người gọi đã trả trước, phần nhỏ hơn lúc mint refund cho storage_payer
+     * - Trả về số bytes đã refund cho storage_payer (không tính vào thay đổi storage của người gọi)
+     */
+    pub(crate) fn internal_replace_token_metadata(
+        &mut self,
+        token_id: &TokenId,
+        metadata: &TokenMetadata,
+        storage_payer: AccountId,
+    ) -> u64 {
+        let growth = self.token_metadata_growth.get(token_id).unwrap_or(0);
+        let before_storage_usage = env::storage_usage();
+
+        self.token_metadata_by_id.insert(token_id, metadata);
+
+        let after_storage_usage = env::storage_usage();
+        let (growth, released) = if after_storage_usage >= before_storage_usage {
+            (growth + after_storage_usage - before_storage_usage, 0)
+        } else {
+            let shrink = before_storage_usage - after_storage_usage;
+            (growth.saturating_sub(shrink), shrink.saturating_sub(growth))
+        };
+
+        if growth > 0 {
+            self.token_metadata_growth.insert(token_id, &growth);
+        } else {
+            self.token_metadata_growth.remove(token_id);
+        }
+        refund_storage_released(storage_payer, released);
+
+        released
+    }
+
+    // Token soulbound không thể transfer hoặc approve cho account khác
+    pub(crate) fn assert_not_soulbound(&self, token_id: &TokenId) {
+        assert!(
+            !self.soulbound_tokens.contains_key(token_id),
+            "Soulbound token cannot be transferred"
+        );
+    }
+
     // Return data token cũ trước khi thực hiện transfer
     /**
      * - Kiểm tra token_id có tồn tại không?
@@ -67,11 +271,27 @@ impl Contract {
     ) -> Token {
         // Kiểm tra token_id có tồn tại không?
         let token = self.tokens_by_id.get(token_id).expect("Not found token");
-        // sender_id có phải là owner của token hay không?
-        // Nếu sender_id không phải owner của token -> Check xem sender_id có phải approval_id không (có quyền transfer token thay owner không)
-        // Chỉ có owner của Token HOẶC người được approved mới có quyền transfer Token
-        if sender_id != &token.owner_id {
-            if !token.approved_account_ids.contains_key(sender_id) {
+        // Token soulbound không thể transfer
+        self.assert_not_soulbound(token_id);
+        // Token đã hết hạn chỉ có thể được chuyển bởi issuer (nếu contract cho phép)
+        if self.internal_is_expired(token_id) {
+            assert!(
+                self.issuer_transfers_expired && sender_id == &self.internal_token_issuer(token_id),
+                "Token has expired"
+            );
+        } else if sender_id != &token.owner_id
+            && !self.internal_is_operator(&token.owner_id, sender_id)
+        {
+            // Operator của owner được chuyển mọi token của owner mà không cần approval riêng cho từng token
+            // sender_id có phải là owner của token hay không?
+            // Nếu sender_id không phải owner của token -> Check xem sender_id có phải approval_id không (có quyền transfer token thay owner không)
+            // Chỉ có owner của Token HOẶC người được approved mới có quyền transfer Token
+            // Approval đã hết hạn được coi như không tồn tại
+            let is_approved = match token.approved_account_ids.get(sender_id) {
+                Some(approval_id) => !self.internal_is_approval_expired(token_id, *approval_id),
+                None => false,
+            };
+            if !is_approved {
                 env::panic("Sender must be the token owner or the approved account".as_bytes());
             }
 
@@ -138,4 +358,78 @@ impl Contract {
         // Return token cũ
         token
     }
+
+    // Xoá token khỏi contract, refund storage và log event nft_burn
+    // - Phí approve (do owner trả khi approve) refund cho owner
+    // - Phí lưu trữ của token refund cho người đã trả phí khi mint
+    // Return data token trước khi bị burn
+    pub(crate) fn internal_burn(
+        &mut self,
+        token_id: &TokenId,
+        authorized_id: Option<AccountId>,
+        memo: Option<String>,
+    ) -> Token {
+        let mut token = self.tokens_by_id.get(token_id).expect("Not found token");
+
+        // Phí approve được tính theo bytes_for_approved_account_id (không phải số bytes thực tế)
+        // -> Xoá approvals trước rồi mới đo lượng storage được giải phóng để không refund 2 lần
+        let approvals_storage: u64 = token
+            .approved_account_ids
+            .keys()
+            .map(bytes_for_approved_account_id)
+            .sum();
+        let approved_account_ids = std::mem::take(&mut token.approved_account_ids);
+        if !approved_account_ids.is_empty() {
+            self.tokens_by_id.insert(token_id, &token);
+        }
+        let expirations_storage =
+            self.internal_remove_approval_expirations(token_id, approved_account_ids.values());
+        refund_storage_released(
+            token.owner_id.clone(),
+            approvals_storage + expirations_storage,
+        );
+
+        // Lịch sử do nhiều người trả phí (người mint, các người transfer) -> Xoá ngoài khoảng đo, không refund cho ai
+        self.token_history.remove(token_id);
+        // Thời hạn còn lại (nếu có) thuộc approvals của owner cũ đang chờ nft_resolve_transfer
+        // -> Xoá luôn để token mint lại với cùng token_id không bị ảnh hưởng
+        self.approval_expirations.remove(token_id);
+
+        // Phần metadata tăng thêm (và bản ghi của nó) do owner của contract trả phí khi cập nhật metadata
+        let before_storage_usage = env::storage_usage();
+        let growth = self.token_metadata_growth.remove(token_id).unwrap_or(0);
+        let growth_storage = growth + before_storage_usage - env::storage_usage();
+
+        let storage_payer = self.internal_storage_payer(token_id, &token.owner_id);
+        let before_storage_usage = env::storage_usage();
+
+        self.tokens_by_id.remove(token_id);
+        self.token_metadata_by_id.remove(token_id);
+        self.soulbound_tokens.remove(token_id);
+        self.token_storage_payers.remove(token_id);
+        self.internal_remove_token_from_owner(token_id, &token.owner_id);
+
+        // Refund phí storage cho người đã trả phí lưu trữ của token, phần tăng thêm refund cho owner của contract
+        refund_storage_released(
+            storage_payer,
+            before_storage_usage - env::storage_usage() - growth,
+        );
+        refund_storage_released(self.owner_id.clone(), growth_storage);
+
+        // NFT BURN LOG
+        let nft_burn_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: token.owner_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+                authorized_id,
+                memo,
+            }]),
+        };
+        env::log(nft_burn_log.to_string().as_bytes());
+
+        token.approved_account_ids = approved_account_ids;
+        token
+    }
 }
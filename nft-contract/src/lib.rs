@@ -1,5 +1,5 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, UnorderedMap, UnorderedSet};
+use near_sdk::collections::{LazyOption, LookupSet, UnorderedMap, UnorderedSet, Vector};
 use near_sdk::json_types::{Base64VecU8, U128};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{collections::LookupMap, AccountId};
@@ -11,32 +11,59 @@ use std::collections::HashMap;
 
 pub type TokenId = String;
 
+// Tổng royalty tối đa mặc định của 1 token: 50%
+pub const DEFAULT_MAX_ROYALTY: u32 = 5_000;
+
 pub use crate::approval::*;
+pub use crate::drop::*;
 pub use crate::enumeration::*;
 pub use crate::event::*;
+pub use crate::history::*;
 pub use crate::internal::*;
 pub use crate::metadata::*;
 pub use crate::mint::*;
+pub use crate::minter::*;
 pub use crate::nft_core::*;
+pub use crate::pause::*;
+pub use crate::pool::*;
+pub use crate::reveal::*;
 pub use crate::royalty::*;
+pub use crate::series::*;
+pub use crate::storage::*;
+pub use crate::upgrade::*;
 use crate::utils::*;
 
 mod approval;
+mod burn;
+mod drop;
 mod enumeration;
 mod event;
+mod history;
 mod internal;
 mod metadata;
 mod mint;
+mod minter;
 mod nft_core;
+mod owner;
+mod pause;
+mod pool;
+mod reveal;
 mod royalty;
+mod series;
+mod storage;
+mod upgrade;
 mod utils;
+mod validity;
 
 // State cơ bản của NFT contract
 #[near_bindgen]
+// Note: Thay đổi layout của struct này cần migrate state, xem upgrade.rs
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
-struct Contract {
+pub struct Contract {
     pub owner_id: AccountId,
 
+    pub pending_owner_id: Option<AccountId>, // Owner mới đang chờ accept (chuyển quyền owner 2 bước)
+
     pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>, // Lưu danh sách token mà user sở hữu
 
     pub tokens_by_id: LookupMap<TokenId, Token>, // Mapping token id với các data mở rộng của Token (Danh sách tất cả token đang có trong contract)
@@ -44,6 +71,54 @@ struct Contract {
     pub token_metadata_by_id: UnorderedMap<TokenId, TokenMetadata>, // Mapping token id với token metadata
 
     pub metadata: LazyOption<NFTContractMetadata>,
+
+    pub series_by_id: UnorderedMap<SeriesId, Series>, // Mapping series id với thông tin series
+
+    pub next_series_id: SeriesId, // Id của series tiếp theo
+
+    pub minters: UnorderedMap<AccountId, Minter>, // Danh sách các account được phép mint (ngoài owner)
+
+    pub max_royalty: u32, // Tổng royalty tối đa của 1 token, tính theo basis points (10000 = 100%)
+
+    pub soulbound_tokens: LookupMap<TokenId, AccountId>, // Mapping token soulbound với issuer (người có quyền burn token)
+
+    pub issuer_transfers_expired: bool, // Cho phép issuer chuyển token đã hết hạn (ex. thu hồi vé, membership)
+
+    pub approval_expirations: LookupMap<TokenId, HashMap<u64, u64>>, // Mapping token id với thời hạn của các approval (approval id -> expires_at)
+
+    pub operators: LookupMap<AccountId, UnorderedSet<AccountId>>, // Mapping owner với các operator được chuyển toàn bộ token của owner
+
+    pub paused: PauseStatus, // Các chức năng đang bị tạm dừng
+
+    pub storage_balances: LookupMap<AccountId, Balance>, // Số dư storage nạp trước (NEP-145) còn có thể sử dụng của từng account
+
+    pub token_history: LookupMap<TokenId, Vec<TokenHistoryEntry>>, // Mapping token id với lịch sử sở hữu của token
+
+    pub next_token_id: u64, // Số thứ tự của token_id tự sinh tiếp theo
+
+    pub token_id_prefix: String, // Prefix của token_id tự sinh
+
+    pub reveal_root: Option<CryptoHash>, // Merkle root của metadata cuối cùng (reveal sau khi mint)
+
+    pub mint_pool: Vector<MintPoolEntry>, // Pool metadata chưa được mint, dùng cho nft_mint_random
+
+    pub mint_pool_loaded: u64, // Tổng số metadata đã nạp vào pool
+
+    pub drop_config: Option<DropConfig>, // Cấu hình các phase của drop
+
+    pub drop_allowlist: LookupSet<AccountId>, // Danh sách account được mua trong phase allowlist
+
+    pub drop_wallet_minted: LookupMap<AccountId, DropWalletMinted>, // Số token mỗi ví đã mua trong từng phase
+
+    pub drop_treasury_near: Balance, // Số NEAR thu được từ drop, owner chưa rút
+
+    pub drop_treasury_ft: UnorderedMap<AccountId, Balance>, // Số FT thu được từ drop theo từng FT contract
+
+    pub token_storage_payers: LookupMap<TokenId, AccountId>, // Mapping token id với account đã trả phí lưu trữ khi mint (token cũ chưa có thì mặc định là owner)
+
+    pub default_royalty: HashMap<AccountId, u32>, // Royalty mặc định cho token mint không truyền perpetual_royalties
+
+    pub token_metadata_growth: LookupMap<TokenId, u64>, // Số bytes metadata tăng thêm so với lúc mint mà owner của contract đã trả phí
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -55,14 +130,34 @@ pub enum StorageKey {
     TokenPerOwnerInnerKey {
         account_id_hash: CryptoHash, // Để đảm bảo các account_id không trùng nhau
     },
+    SeriesByIdKey,
+    MintersKey,
+    SoulboundTokensKey,
+    ApprovalExpirationsKey,
+    OperatorsKey,
+    OperatorsInnerKey {
+        account_id_hash: CryptoHash,
+    },
+    StorageBalancesKey,
+    TokenHistoryKey,
+    MintPoolKey,
+    DropAllowlistKey,
+    DropWalletMintedKey,
+    DropTreasuryFtKey,
+    TokenStoragePayersKey,
+    TokenMetadataGrowthKey,
 }
 
 #[near_bindgen]
 impl Contract {
     #[init]
     pub fn new(owner_id: AccountId, token_metadata: NFTContractMetadata) -> Self {
+        token_metadata.assert_valid();
+        write_state_version();
+
         Self {
             owner_id,
+            pending_owner_id: None,
             metadata: LazyOption::new(
                 StorageKey::ContractMetadataKey.try_to_vec().unwrap(),
                 Some(&token_metadata),
@@ -72,6 +167,40 @@ impl Contract {
             token_metadata_by_id: UnorderedMap::new(
                 StorageKey::TokenMetadataByIdKey.try_to_vec().unwrap(),
             ),
+            series_by_id: UnorderedMap::new(StorageKey::SeriesByIdKey.try_to_vec().unwrap()),
+            next_series_id: 0,
+            minters: UnorderedMap::new(StorageKey::MintersKey.try_to_vec().unwrap()),
+            max_royalty: DEFAULT_MAX_ROYALTY,
+            soulbound_tokens: LookupMap::new(StorageKey::SoulboundTokensKey.try_to_vec().unwrap()),
+            issuer_transfers_expired: false,
+            approval_expirations: LookupMap::new(
+                StorageKey::ApprovalExpirationsKey.try_to_vec().unwrap(),
+            ),
+            operators: LookupMap::new(StorageKey::OperatorsKey.try_to_vec().unwrap()),
+            paused: PauseStatus::default(),
+            storage_balances: LookupMap::new(StorageKey::StorageBalancesKey.try_to_vec().unwrap()),
+            token_history: LookupMap::new(StorageKey::TokenHistoryKey.try_to_vec().unwrap()),
+            next_token_id: 0,
+            token_id_prefix: String::new(),
+            reveal_root: None,
+            mint_pool: Vector::new(StorageKey::MintPoolKey.try_to_vec().unwrap()),
+            mint_pool_loaded: 0,
+            drop_config: None,
+            drop_allowlist: LookupSet::new(StorageKey::DropAllowlistKey.try_to_vec().unwrap()),
+            drop_wallet_minted: LookupMap::new(
+                StorageKey::DropWalletMintedKey.try_to_vec().unwrap(),
+            ),
+            drop_treasury_near: 0,
+            drop_treasury_ft: UnorderedMap::new(
+                StorageKey::DropTreasuryFtKey.try_to_vec().unwrap(),
+            ),
+            token_storage_payers: LookupMap::new(
+                StorageKey::TokenStoragePayersKey.try_to_vec().unwrap(),
+            ),
+            default_royalty: HashMap::new(),
+            token_metadata_growth: LookupMap::new(
+                StorageKey::TokenMetadataGrowthKey.try_to_vec().unwrap(),
+            ),
         }
     }
 
@@ -96,11 +225,14 @@ impl Contract {
 mod tests {
     use super::*;
 
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::test_utils::{
+        accounts, get_created_receipts, testing_env_with_promise_results, VMContextBuilder,
+    };
     use near_sdk::testing_env;
     use near_sdk::MockedBlockchain;
 
     const MINT_STORAGE_COST: u128 = 58_700_000_000_000_000_000_000;
+    const ONE_NEAR: u128 = 1_000_000_000_000_000_000_000_000;
 
     fn get_context(is_view: bool) -> VMContextBuilder {
         let mut builder = VMContextBuilder::new();
@@ -130,6 +262,79 @@ mod tests {
         }
     }
 
+    #[derive(Deserialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct MockedReceipt {
+        receiver_id: AccountId,
+        actions: Vec<HashMap<String, MockedAction>>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct MockedAction {
+        #[serde(default)]
+        deposit: Balance,
+    }
+
+    // Tổng số yoctoNEAR contract đã transfer cho account_id kể từ lần testing_env! gần nhất
+    fn transferred_to(account_id: &AccountId) -> Balance {
+        let receipts = near_sdk::serde_json::to_string(&get_created_receipts()).unwrap();
+        let receipts: Vec<MockedReceipt> = near_sdk::serde_json::from_str(&receipts).unwrap();
+
+        receipts
+            .iter()
+            .filter(|receipt| &receipt.receiver_id == account_id)
+            .flat_map(|receipt| receipt.actions.iter())
+            .filter_map(|action| action.get("Transfer"))
+            .map(|transfer| transfer.deposit)
+            .sum()
+    }
+
+    // Mint token cho accounts(0), approve cho accounts(2) rồi nft_transfer_call sang accounts(1)
+    // Return contract và approvals của token trước khi transfer
+    fn setup_transfer_call(
+        context: &mut VMContextBuilder,
+        token_id: &TokenId,
+    ) -> (Contract, HashMap<AccountId, u64>) {
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.nft_mint(
+            Some(token_id.clone()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+        // --- Nạp trước phí lưu trữ lịch sử transfer cho sender và receiver
+        contract.storage_deposit(None, None);
+        contract.storage_deposit(Some(accounts(1).to_string()), None);
+        contract.nft_approve(token_id.clone(), accounts(2).to_string(), None, None);
+        let approved_account_ids = contract
+            .nft_token(token_id.clone())
+            .unwrap()
+            .approved_account_ids;
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        contract.nft_transfer_call(
+            accounts(1).to_string(),
+            token_id.clone(),
+            None,
+            0,
+            "".to_string(),
+        );
+
+        (contract, approved_account_ids)
+    }
+
     #[test]
     fn test_mint_token() {
         let mut context = get_context(false);
@@ -146,10 +351,11 @@ mod tests {
 
         let token_id = "ZNG_NFT".to_string();
         contract.nft_mint(
-            token_id.clone(),
+            Some(token_id.clone()),
             get_sample_metadata(),
             accounts(0).to_string(),
             None,
+            None,
         );
 
         let token = contract.nft_token(token_id.clone()).unwrap();
@@ -178,11 +384,14 @@ mod tests {
         // --- Tạo 1 token có id là zng_nft ---
         let token_id = "zng_nft".to_owned();
         contract.nft_mint(
-            token_id.clone(),
+            Some(token_id.clone()),
             get_sample_metadata(),
             accounts(0).to_string(),
             None,
+            None,
         );
+        // --- Nạp trước phí lưu trữ lịch sử transfer
+        contract.storage_deposit(None, None);
 
         let token = contract.nft_token(token_id.clone()).unwrap();
         // --- Kiểm tra các thông tin về owner ---
@@ -200,5 +409,2172 @@ mod tests {
         assert_eq!(new_token.owner_id, accounts(1).to_string());
         assert_eq!(new_token.token_id, token_id);
         assert_eq!(get_sample_metadata(), new_token.metadata);
+        // --- Lịch sử của token gồm lần mint và lần transfer
+        let history = contract.nft_token_history(token_id, None, Some(10));
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].kind, TokenHistoryKind::Mint);
+        assert_eq!(history[1].kind, TokenHistoryKind::Transfer);
+        assert_eq!(history[1].old_owner_id, Some(accounts(0).to_string()));
+        assert_eq!(history[1].new_owner_id, accounts(1).to_string());
+    }
+
+    #[test]
+    fn test_transfer_history_bounded() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        let token_id = contract.nft_mint(
+            None,
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+        // --- Nạp trước phí lưu trữ lịch sử transfer cho cả 2 bên
+        contract.storage_deposit(None, None);
+        contract.storage_deposit(Some(accounts(1).to_string()), None);
+
+        // --- Transfer qua lại nhiều lần, mỗi lần sender trả phí cho entry mới kể cả khi lịch sử đã đầy
+        let owners = [accounts(0), accounts(1)];
+        for i in 0..MAX_TOKEN_HISTORY + 5 {
+            testing_env!(context
+                .storage_usage(env::storage_usage())
+                .attached_deposit(1)
+                .predecessor_account_id(owners[i % 2].clone())
+                .build());
+            let available = contract
+                .storage_balance_of(owners[i % 2].to_string())
+                .unwrap()
+                .available;
+            contract.nft_transfer(
+                owners[(i + 1) % 2].to_string(),
+                token_id.clone(),
+                0,
+                Some("m".repeat(MAX_HISTORY_MEMO_LENGTH * 2)),
+            );
+            assert!(
+                contract
+                    .storage_balance_of(owners[i % 2].to_string())
+                    .unwrap()
+                    .available
+                    .0
+                    < available.0
+            );
+        }
+
+        // --- Chỉ giữ MAX_TOKEN_HISTORY entry gần nhất, memo bị cắt ngắn
+        let history = contract.nft_token_history(token_id, None, Some(100));
+        assert_eq!(history.len(), MAX_TOKEN_HISTORY);
+        assert!(history
+            .iter()
+            .all(|entry| entry.kind == TokenHistoryKind::Transfer));
+        assert_eq!(
+            history[0].memo.as_ref().unwrap().len(),
+            MAX_HISTORY_MEMO_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_burn_nft() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let token_id = "zng_nft".to_owned();
+        contract.nft_mint(
+            Some(token_id.clone()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+
+        contract.nft_burn(token_id.clone(), None);
+
+        // --- Token đã bị xoá khỏi contract
+        assert!(contract.nft_token(token_id).is_none());
+        assert_eq!(contract.nft_total_supply(), U128(0));
+        assert_eq!(
+            contract.nft_supply_for_owner(accounts(0).to_string()),
+            U128(0)
+        );
+    }
+
+    #[test]
+    fn test_sponsored_mint_storage_payer() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        // --- accounts(0) mint hộ và trả phí lưu trữ cho accounts(1)
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 2)
+            .predecessor_account_id(accounts(0))
+            .build());
+        let sponsored_id = contract.nft_mint(
+            None,
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+        let own_id = contract.nft_mint(
+            None,
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+        assert_eq!(
+            contract.nft_storage_payer(sponsored_id.clone()),
+            accounts(0).to_string()
+        );
+        assert_eq!(
+            contract.nft_storage_payer(own_id.clone()),
+            accounts(0).to_string()
+        );
+
+        // --- Sau khi transfer, refund của cả 2 token vẫn về accounts(0) (người trả phí khi mint)
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.storage_deposit(None, None);
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        contract.nft_transfer(accounts(2).to_string(), sponsored_id.clone(), 0, None);
+        assert_eq!(
+            contract.nft_storage_payer(sponsored_id.clone()),
+            accounts(0).to_string()
+        );
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.storage_deposit(None, None);
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        contract.nft_transfer(accounts(2).to_string(), own_id.clone(), 0, None);
+        assert_eq!(contract.nft_storage_payer(own_id), accounts(0).to_string());
+
+        // --- Burn xoá storage payer của token
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(2))
+            .attached_deposit(1)
+            .build());
+        contract.nft_burn(sponsored_id.clone(), None);
+        assert!(!contract.token_storage_payers.contains_key(&sponsored_id));
+    }
+
+    #[test]
+    fn test_sponsored_mint_approval_refunds() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        // --- accounts(0) mint hộ và trả phí lưu trữ cho accounts(1)
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        let token_id = contract.nft_mint(
+            None,
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+
+        // --- Phí approve do owner của token trả -> Revoke refund cho owner, không phải người mint hộ
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.nft_approve(token_id.clone(), accounts(2).to_string(), None, Some(1_000));
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        contract.nft_revoke(token_id.clone(), accounts(2).to_string());
+        assert!(transferred_to(&accounts(1).to_string()) > 0);
+        assert_eq!(transferred_to(&accounts(0).to_string()), 0);
+
+        // --- Burn: phí approve refund cho owner, phí lưu trữ của token refund cho người mint hộ
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .build());
+        contract.nft_approve(token_id.clone(), accounts(2).to_string(), None, Some(1_000));
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        contract.nft_burn(token_id, None);
+        // --- Owner nhận lại cả phí lưu trữ thời hạn của approval
+        assert!(
+            transferred_to(&accounts(1).to_string())
+                > Balance::from(bytes_for_approved_account_id(&accounts(2).to_string()))
+                    * env::storage_byte_cost()
+        );
+        assert!(transferred_to(&accounts(0).to_string()) > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner of the NFT can burn it")]
+    fn test_burn_nft_not_owner() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let token_id = "zng_nft".to_owned();
+        contract.nft_mint(
+            Some(token_id.clone()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+
+        testing_env!(context
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(1))
+            .build());
+
+        contract.nft_burn(token_id, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Soulbound token cannot be transferred")]
+    fn test_transfer_soulbound_token() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let token_id = "zng_nft".to_owned();
+        contract.nft_mint(
+            Some(token_id.clone()),
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            Some(true),
+        );
+        assert!(contract.nft_token(token_id.clone()).unwrap().soulbound);
+
+        testing_env!(context
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(1))
+            .build());
+
+        contract.nft_transfer(accounts(2).to_string(), token_id, 0, None);
+    }
+
+    #[test]
+    fn test_issuer_burn_soulbound_token() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let token_id = "zng_nft".to_owned();
+        contract.nft_mint(
+            Some(token_id.clone()),
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            Some(true),
+        );
+
+        // --- Issuer (người mint) thu hồi token của accounts(1)
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+
+        contract.nft_burn(token_id.clone(), None);
+
+        assert!(contract.nft_token(token_id).is_none());
+        assert_eq!(
+            contract.nft_supply_for_owner(accounts(1).to_string()),
+            U128(0)
+        );
+    }
+
+    // Mint token zng_nft cho accounts(1), hết hạn tại thời điểm 1000ms
+    fn setup_expiring_token(context: &mut VMContextBuilder) -> Contract {
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.nft_mint(
+            Some("zng_nft".to_owned()),
+            TokenMetadata {
+                expires_at: Some(1_000),
+                ..get_sample_metadata()
+            },
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+        contract.storage_deposit(None, None);
+        assert!(contract.nft_is_valid("zng_nft".to_owned()));
+
+        // --- Thời gian tính theo nanoseconds -> 2000ms
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .block_timestamp(2_000_000_000)
+            .attached_deposit(1)
+            .build());
+
+        contract
+    }
+
+    #[test]
+    #[should_panic(expected = "Token has expired")]
+    fn test_transfer_expired_token() {
+        let mut context = get_context(false);
+        let mut contract = setup_expiring_token(&mut context);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.nft_transfer(accounts(2).to_string(), "zng_nft".to_owned(), 0, None);
+    }
+
+    #[test]
+    fn test_issuer_transfers_expired_token() {
+        let mut context = get_context(false);
+        let mut contract = setup_expiring_token(&mut context);
+
+        contract.set_issuer_transfers_expired(true);
+        contract.nft_transfer(accounts(0).to_string(), "zng_nft".to_owned(), 0, None);
+
+        let token = contract.nft_token("zng_nft".to_owned()).unwrap();
+        assert_eq!(token.owner_id, accounts(0).to_string());
+        assert!(!token.is_valid);
+    }
+
+    #[test]
+    fn test_burn_expired_token() {
+        let mut context = get_context(false);
+        let mut contract = setup_expiring_token(&mut context);
+
+        assert!(!contract.nft_is_valid("zng_nft".to_owned()));
+
+        // --- Bất kỳ ai cũng có thể burn token đã hết hạn
+        testing_env!(context
+            .attached_deposit(0)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.nft_burn_expired(vec!["zng_nft".to_owned()]);
+
+        assert!(contract.nft_token("zng_nft".to_owned()).is_none());
+        assert_eq!(
+            contract.nft_supply_for_owner(accounts(1).to_string()),
+            U128(0)
+        );
+    }
+
+    // Mint token zng_nft cho accounts(0), approve accounts(2) tới thời điểm 1000ns rồi cho thời gian trôi qua
+    fn setup_expiring_approval(context: &mut VMContextBuilder) -> Contract {
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.nft_mint(
+            Some("zng_nft".to_owned()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+        contract.nft_approve(
+            "zng_nft".to_owned(),
+            accounts(2).to_string(),
+            None,
+            Some(1_000),
+        );
+        assert!(contract.nft_is_approved("zng_nft".to_owned(), accounts(2).to_string(), Some(0)));
+        assert!(contract.nft_is_approved("zng_nft".to_owned(), accounts(2).to_string(), None));
+        assert!(!contract.nft_is_approved("zng_nft".to_owned(), accounts(2).to_string(), Some(1)));
+        assert_eq!(
+            contract.nft_approval_expires_at("zng_nft".to_owned(), accounts(2).to_string()),
+            Some(1_000)
+        );
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .block_timestamp(2_000)
+            .attached_deposit(1)
+            .build());
+
+        contract
+    }
+
+    #[test]
+    #[should_panic(expected = "Sender must be the token owner or the approved account")]
+    fn test_transfer_with_expired_approval() {
+        let mut context = get_context(false);
+        let mut contract = setup_expiring_approval(&mut context);
+
+        assert!(!contract.nft_is_approved("zng_nft".to_owned(), accounts(2).to_string(), Some(0)));
+        assert!(!contract.nft_is_approved("zng_nft".to_owned(), accounts(2).to_string(), None));
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.nft_transfer(accounts(1).to_string(), "zng_nft".to_owned(), 0, None);
+    }
+
+    #[test]
+    fn test_clear_expired_approvals() {
+        let mut context = get_context(false);
+        let mut contract = setup_expiring_approval(&mut context);
+
+        testing_env!(context
+            .attached_deposit(0)
+            .predecessor_account_id(accounts(3))
+            .build());
+        assert_eq!(
+            contract.nft_clear_expired_approvals("zng_nft".to_owned()),
+            1
+        );
+
+        let token = contract.nft_token("zng_nft".to_owned()).unwrap();
+        assert!(token.approved_account_ids.is_empty());
+        assert!(contract
+            .approval_expirations
+            .get(&"zng_nft".to_owned())
+            .is_none());
+    }
+
+    #[test]
+    fn test_operator_transfer() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        for token_id in ["zng_nft_1", "zng_nft_2"] {
+            contract.nft_mint(
+                Some(token_id.to_owned()),
+                get_sample_metadata(),
+                accounts(0).to_string(),
+                None,
+                None,
+            );
+        }
+        contract.nft_approve_operator(accounts(2).to_string());
+        // --- Operator trả phí lưu trữ lịch sử transfer
+        contract.storage_deposit(Some(accounts(2).to_string()), None);
+        assert_eq!(
+            contract.nft_operators(accounts(0).to_string(), None, Some(10)),
+            vec![accounts(2).to_string()]
+        );
+
+        // --- Operator chuyển token của owner mà không cần approve từng token
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.nft_transfer(accounts(1).to_string(), "zng_nft_1".to_owned(), 0, None);
+        assert_eq!(
+            contract.nft_token("zng_nft_1".to_owned()).unwrap().owner_id,
+            accounts(1).to_string()
+        );
+        // --- Operator của owner cũ không có quyền với token của owner mới
+        assert!(!contract.nft_is_operator(accounts(1).to_string(), accounts(2).to_string()));
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.nft_revoke_operator(accounts(2).to_string());
+        assert!(!contract.nft_is_operator(accounts(0).to_string(), accounts(2).to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfers are paused")]
+    fn test_transfer_when_paused() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.nft_mint(
+            Some("zng_nft".to_owned()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+
+        testing_env!(context.attached_deposit(1).build());
+        contract.set_paused(None, Some(true), None);
+        assert!(contract.get_paused().transfers);
+        assert!(!contract.get_paused().mint);
+
+        // --- Views vẫn hoạt động khi bị tạm dừng
+        assert!(contract.nft_token("zng_nft".to_owned()).is_some());
+
+        contract.nft_transfer(accounts(1).to_string(), "zng_nft".to_owned(), 0, None);
+    }
+
+    #[test]
+    fn test_mint_with_prepaid_storage() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        // --- Nạp trước phí storage 1 lần
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.storage_deposit(None, None);
+        let balance = contract
+            .storage_balance_of(accounts(0).to_string())
+            .unwrap();
+        assert_eq!(
+            balance.total.0 - balance.available.0,
+            contract.storage_balance_bounds().min.0
+        );
+
+        // --- Mint không cần đính kèm deposit
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .build());
+        contract.nft_mint(
+            Some("zng_nft".to_owned()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+        let available = contract
+            .storage_balance_of(accounts(0).to_string())
+            .unwrap()
+            .available;
+        assert!(available.0 < balance.available.0);
+
+        testing_env!(context.attached_deposit(1).build());
+        assert_eq!(contract.storage_withdraw(None).available, U128(0));
+        assert!(contract.storage_unregister(None));
+        assert!(contract
+            .storage_balance_of(accounts(0).to_string())
+            .is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "or prepay storage to cover storage")]
+    fn test_mint_without_storage() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.nft_mint(
+            Some("zng_nft".to_owned()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+    }
+
+    // Commit metadata cuối cùng của 2 token zng_nft_1, zng_nft_2 rồi mint với metadata tạm
+    fn setup_hidden_drop(context: &mut VMContextBuilder) -> (Contract, Vec<TokenMetadata>) {
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        let final_metadata: Vec<TokenMetadata> = ["Rare", "Common"]
+            .iter()
+            .map(|title| TokenMetadata {
+                title: Some(title.to_string()),
+                ..get_sample_metadata()
+            })
+            .collect();
+        let leaves: Vec<CryptoHash> = ["zng_nft_1", "zng_nft_2"]
+            .iter()
+            .zip(final_metadata.iter())
+            .map(|(token_id, metadata)| reveal_leaf(&token_id.to_string(), metadata))
+            .collect();
+        let root = reveal_node(&leaves[0], &leaves[1]);
+
+        testing_env!(context.attached_deposit(1).build());
+        contract.set_reveal_commitment(Base64VecU8(root.to_vec()));
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 2)
+            .build());
+        for token_id in ["zng_nft_1", "zng_nft_2"] {
+            contract.nft_mint(
+                Some(token_id.to_owned()),
+                TokenMetadata {
+                    title: Some("Hidden".to_owned()),
+                    ..get_sample_metadata()
+                },
+                accounts(1).to_string(),
+                None,
+                None,
+            );
+        }
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .build());
+
+        (contract, final_metadata)
+    }
+
+    #[test]
+    fn test_reveal_metadata() {
+        let mut context = get_context(false);
+        let (mut contract, final_metadata) = setup_hidden_drop(&mut context);
+
+        let leaf_2 = reveal_leaf(&"zng_nft_2".to_string(), &final_metadata[1]);
+        contract.nft_reveal(vec![RevealArgs {
+            token_id: "zng_nft_1".to_string(),
+            metadata: final_metadata[0].clone(),
+            proof: vec![Base64VecU8(leaf_2.to_vec())],
+        }]);
+
+        let metadata = contract
+            .nft_token("zng_nft_1".to_string())
+            .unwrap()
+            .metadata;
+        assert_eq!(metadata.title, Some("Rare".to_string()));
+        let metadata = contract
+            .nft_token("zng_nft_2".to_string())
+            .unwrap()
+            .metadata;
+        assert_eq!(metadata.title, Some("Hidden".to_string()));
+    }
+
+    #[test]
+    fn test_reveal_metadata_prepaid_storage() {
+        let mut context = get_context(false);
+        let (mut contract, final_metadata) = setup_hidden_drop(&mut context);
+        contract.storage_deposit(None, None);
+        let available = contract
+            .storage_balance_of(accounts(0).to_string())
+            .unwrap()
+            .available;
+
+        // --- Chỉ đính kèm 1 yoctoNEAR -> Phí tăng thêm trừ vào số dư storage đã nạp
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        let leaf_1 = reveal_leaf(&"zng_nft_1".to_string(), &final_metadata[0]);
+        contract.nft_reveal(vec![RevealArgs {
+            token_id: "zng_nft_2".to_string(),
+            metadata: final_metadata[1].clone(),
+            proof: vec![Base64VecU8(leaf_1.to_vec())],
+        }]);
+
+        assert!(
+            contract
+                .storage_balance_of(accounts(0).to_string())
+                .unwrap()
+                .available
+                .0
+                < available.0
+        );
+        assert!(contract
+            .token_metadata_growth
+            .get(&"zng_nft_2".to_string())
+            .is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Metadata of token zng_nft_2 does not match the reveal commitment")]
+    fn test_reveal_metadata_not_committed() {
+        let mut context = get_context(false);
+        let (mut contract, final_metadata) = setup_hidden_drop(&mut context);
+
+        // --- Đổi token zng_nft_2 thành metadata "Rare"
+        let leaf_1 = reveal_leaf(&"zng_nft_1".to_string(), &final_metadata[0]);
+        contract.nft_reveal(vec![RevealArgs {
+            token_id: "zng_nft_2".to_string(),
+            metadata: final_metadata[0].clone(),
+            proof: vec![Base64VecU8(leaf_1.to_vec())],
+        }]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Metadata is locked by the reveal commitment, use nft_reveal")]
+    fn test_update_metadata_after_reveal_commitment() {
+        let mut context = get_context(false);
+        let (mut contract, final_metadata) = setup_hidden_drop(&mut context);
+
+        // --- Owner không thể bỏ qua commitment bằng nft_metadata_update
+        contract.nft_metadata_update("zng_nft_2".to_string(), final_metadata[0].clone(), None);
+    }
+
+    #[test]
+    fn test_mint_random_from_pool() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 3)
+            .random_seed(vec![7; 32])
+            .build());
+        let titles = ["Gold", "Silver", "Bronze"];
+        contract.nft_load_mint_pool(
+            titles
+                .iter()
+                .map(|title| TokenMetadata {
+                    title: Some(title.to_string()),
+                    ..get_sample_metadata()
+                })
+                .collect(),
+        );
+        assert_eq!(contract.nft_mint_pool_remaining(), 3);
+
+        let token_ids: Vec<TokenId> = (0..2)
+            .map(|_| contract.nft_mint_random(accounts(1).to_string()))
+            .collect();
+        assert_eq!(contract.nft_mint_pool_remaining(), 1);
+        assert_eq!(contract.nft_mint_pool_minted(), 2);
+
+        // --- Mỗi token nhận 1 metadata khác nhau từ pool
+        let minted_titles: Vec<String> = token_ids
+            .into_iter()
+            .map(|token_id| {
+                contract
+                    .nft_token(token_id)
+                    .unwrap()
+                    .metadata
+                    .title
+                    .unwrap()
+            })
+            .collect();
+        assert_ne!(minted_titles[0], minted_titles[1]);
+        assert!(minted_titles
+            .iter()
+            .all(|title| titles.contains(&title.as_str())));
+    }
+
+    #[test]
+    fn test_mint_random_refunds_pool_loader() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .build());
+        contract.nft_load_mint_pool(vec![get_sample_metadata()]);
+
+        // --- Chuyển quyền owner cho accounts(1) sau khi accounts(0) đã nạp pool
+        testing_env!(context.attached_deposit(1).build());
+        contract.propose_owner(accounts(1).to_string());
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.accept_owner();
+
+        // --- Storage của metadata được xoá khỏi pool refund cho accounts(0) (người đã nạp)
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .build());
+        contract.nft_mint_random(accounts(2).to_string());
+        assert!(transferred_to(&accounts(0).to_string()) > 0);
+        assert_eq!(contract.nft_mint_pool_remaining(), 0);
+    }
+
+    #[test]
+    fn test_mint_random_storage() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .build());
+        contract.nft_load_mint_pool(vec![get_sample_metadata()]);
+        contract.add_minter(accounts(2).to_string(), None, None);
+
+        // --- Minter trả phí lưu trữ từ số dư storage đã nạp
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(2))
+            .build());
+        contract.storage_deposit(None, None);
+        let available = contract
+            .storage_balance_of(accounts(2).to_string())
+            .unwrap()
+            .available
+            .0;
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(0)
+            .build());
+        let token_id = contract.nft_mint_random(accounts(1).to_string());
+        let charged = available
+            - contract
+                .storage_balance_of(accounts(2).to_string())
+                .unwrap()
+                .available
+                .0;
+        // --- Storage của metadata được xoá khỏi pool refund cho owner
+        assert!(charged > 0);
+        assert!(transferred_to(&accounts(0).to_string()) > 0);
+        assert_eq!(transferred_to(&accounts(2).to_string()), 0);
+
+        // --- Burn refund cho minter phí đã trả, trừ phần lịch sử (không được refund)
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.nft_burn(token_id, None);
+        let refunded = transferred_to(&accounts(2).to_string());
+        assert!(refunded > 0 && refunded < charged);
+    }
+
+    // Owner nạp pool 4 metadata, allowlist accounts(1)
+    // Phase allowlist: [0, 1000) giá 1 NEAR, mỗi ví 1 token. Phase public: [1000, 2000) giá 2 (NEAR hoặc FT)
+    fn setup_drop(
+        context: &mut VMContextBuilder,
+        public_ft_contract_id: Option<AccountId>,
+    ) -> Contract {
+        testing_env!(context.build());
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 4)
+            .build());
+        contract.nft_load_mint_pool(vec![get_sample_metadata(); 4]);
+        contract.drop_add_to_allowlist(vec![accounts(1).to_string()]);
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        contract.set_drop(
+            Some(DropPhase {
+                price: U128(ONE_NEAR),
+                ft_contract_id: None,
+                starts_at: 0,
+                ends_at: 1000,
+                max_per_wallet: Some(1),
+                max_supply: None,
+                minted: 0,
+            }),
+            Some(DropPhase {
+                price: U128(2 * ONE_NEAR),
+                ft_contract_id: public_ft_contract_id,
+                starts_at: 1000,
+                ends_at: 2000,
+                max_per_wallet: None,
+                max_supply: Some(2),
+                minted: 0,
+            }),
+        );
+
+        contract
+    }
+
+    #[test]
+    fn test_drop_allowlist_then_public() {
+        let mut context = get_context(false);
+        let mut contract = setup_drop(&mut context, None);
+        assert_eq!(
+            contract.get_drop_active_phase(),
+            Some(DropPhaseKind::Allowlist)
+        );
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(ONE_NEAR + MINT_STORAGE_COST)
+            .build());
+        let token_ids = contract.drop_mint(1);
+        assert_eq!(
+            contract.nft_token(token_ids[0].clone()).unwrap().owner_id,
+            accounts(1).to_string()
+        );
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(2))
+            .attached_deposit(4 * ONE_NEAR + MINT_STORAGE_COST * 2)
+            .block_timestamp(1000)
+            .build());
+        assert_eq!(
+            contract.get_drop_active_phase(),
+            Some(DropPhaseKind::Public)
+        );
+        assert_eq!(contract.drop_mint(2).len(), 2);
+
+        assert_eq!(
+            contract.drop_minted_by(accounts(1).to_string(), DropPhaseKind::Allowlist),
+            1
+        );
+        assert_eq!(
+            contract.drop_minted_by(accounts(2).to_string(), DropPhaseKind::Public),
+            2
+        );
+        assert_eq!(contract.get_drop_treasury(None), U128(5 * ONE_NEAR));
+        assert_eq!(contract.nft_mint_pool_remaining(), 1);
+
+        // --- Owner rút toàn bộ NEAR trong treasury
+        testing_env!(context
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(1)
+            .build());
+        contract.drop_withdraw(None, None, None);
+        assert_eq!(contract.get_drop_treasury(None), U128(0));
+    }
+
+    #[test]
+    fn test_drop_mint_storage() {
+        let mut context = get_context(false);
+        let mut contract = setup_drop(&mut context, None);
+
+        // --- Người mua trả phí lưu trữ của token, storage của pool refund cho owner
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(ONE_NEAR + MINT_STORAGE_COST)
+            .build());
+        let token_ids = contract.drop_mint(1);
+        let charged = MINT_STORAGE_COST - transferred_to(&accounts(1).to_string());
+        assert!(charged > 0);
+        assert!(transferred_to(&accounts(0).to_string()) > 0);
+
+        // --- Burn không refund nhiều hơn phí người mua đã trả
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        contract.nft_burn(token_ids[0].clone(), None);
+        let refunded = transferred_to(&accounts(1).to_string());
+        assert!(refunded > 0 && refunded <= charged);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account is not in the drop allowlist")]
+    fn test_drop_mint_not_allowlisted() {
+        let mut context = get_context(false);
+        let mut contract = setup_drop(&mut context, None);
+
+        testing_env!(context
+            .predecessor_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR + MINT_STORAGE_COST)
+            .build());
+        contract.drop_mint(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Exceeded wallet limit of 1 tokens")]
+    fn test_drop_mint_over_wallet_limit() {
+        let mut context = get_context(false);
+        let mut contract = setup_drop(&mut context, None);
+
+        testing_env!(context
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(2 * ONE_NEAR + MINT_STORAGE_COST * 2)
+            .build());
+        contract.drop_mint(2);
+    }
+
+    #[test]
+    fn test_drop_mint_with_ft() {
+        let mut context = get_context(false);
+        let mut contract = setup_drop(&mut context, Some(accounts(3).to_string()));
+
+        // --- Người mua nạp trước storage để trả phí lưu trữ của token
+        testing_env!(context
+            .predecessor_account_id(accounts(2))
+            .attached_deposit(MINT_STORAGE_COST)
+            .block_timestamp(1000)
+            .build());
+        contract.storage_deposit(None, None);
+
+        // --- FT contract gọi ft_on_transfer, số FT thừa được trả lại
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(3))
+            .attached_deposit(0)
+            .build());
+        let refund = contract.ft_on_transfer(
+            accounts(2).to_string(),
+            U128(5 * ONE_NEAR),
+            "{\"quantity\": 2}".to_string(),
+        );
+        match refund {
+            PromiseOrValue::Value(refund) => assert_eq!(refund, U128(ONE_NEAR)),
+            PromiseOrValue::Promise(_) => panic!("Expected refund value"),
+        }
+        assert_eq!(
+            contract.get_drop_treasury(Some(accounts(3).to_string())),
+            U128(4 * ONE_NEAR)
+        );
+        assert_eq!(
+            contract.nft_supply_for_owner(accounts(2).to_string()),
+            U128(2)
+        );
+    }
+
+    #[test]
+    fn test_batch_mint() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 3)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let tokens = vec![
+            ("zng_nft_1", accounts(1)),
+            ("zng_nft_2", accounts(2)),
+            ("zng_nft_3", accounts(1)),
+        ]
+        .into_iter()
+        .map(|(token_id, receiver_id)| MintArgs {
+            token_id: Some(token_id.to_string()),
+            metadata: get_sample_metadata(),
+            receiver_id: receiver_id.to_string(),
+            perpetual_royalties: None,
+            soulbound: None,
+        })
+        .collect();
+        contract.nft_batch_mint(tokens);
+
+        assert_eq!(contract.nft_total_supply(), U128(3));
+        assert_eq!(
+            contract.nft_supply_for_owner(accounts(1).to_string()),
+            U128(2)
+        );
+        assert_eq!(
+            contract
+                .nft_token("zng_nft_2".to_string())
+                .unwrap()
+                .owner_id,
+            accounts(2).to_string()
+        );
+    }
+
+    #[test]
+    fn test_mint_auto_token_id() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context.attached_deposit(1).build());
+        contract.set_token_id_prefix("zng-".to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 4)
+            .build());
+        // --- token_id "zng-0" đã được mint thủ công -> Bỏ qua khi tự sinh
+        contract.nft_mint(
+            Some("zng-0".to_string()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+        let token_id = contract.nft_mint(
+            None,
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+        assert_eq!(token_id, "zng-1");
+
+        let token_ids = contract.nft_batch_mint(
+            (0..2)
+                .map(|_| MintArgs {
+                    token_id: None,
+                    metadata: get_sample_metadata(),
+                    receiver_id: accounts(1).to_string(),
+                    perpetual_royalties: None,
+                    soulbound: None,
+                })
+                .collect(),
+        );
+        assert_eq!(token_ids, vec!["zng-2".to_string(), "zng-3".to_string()]);
+        assert_eq!(contract.nft_total_supply(), U128(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Token zng_nft already exists")]
+    fn test_batch_mint_existing_token() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 3)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        contract.nft_mint(
+            Some("zng_nft".to_string()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+
+        let tokens = vec!["zng_nft_new", "zng_nft"]
+            .into_iter()
+            .map(|token_id| MintArgs {
+                token_id: Some(token_id.to_string()),
+                metadata: get_sample_metadata(),
+                receiver_id: accounts(1).to_string(),
+                perpetual_royalties: None,
+                soulbound: None,
+            })
+            .collect();
+        contract.nft_batch_mint(tokens);
+    }
+
+    #[test]
+    fn test_batch_mint_generated_id_skips_explicit_ids() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 2)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        // --- Prefix rỗng: token_id tự sinh đầu tiên ("0") trùng với token_id được truyền vào -> Bỏ qua
+        let tokens = vec![Some("0".to_string()), None]
+            .into_iter()
+            .map(|token_id| MintArgs {
+                token_id,
+                metadata: get_sample_metadata(),
+                receiver_id: accounts(1).to_string(),
+                perpetual_royalties: None,
+                soulbound: None,
+            })
+            .collect();
+        let token_ids = contract.nft_batch_mint(tokens);
+
+        assert_eq!(token_ids, vec!["0".to_string(), "1".to_string()]);
+        assert_eq!(contract.nft_total_supply(), U128(2));
+    }
+
+    #[test]
+    fn test_mint_series_editions() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let series_metadata = TokenMetadata {
+            copies: Some(2),
+            ..get_sample_metadata()
+        };
+        let series_id = contract.nft_create_series(series_metadata.clone(), None, None);
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .block_timestamp(1_000_000_000)
+            .build());
+
+        let token_id = contract.nft_mint_edition(series_id, accounts(1).to_string());
+        assert_eq!(token_id, format!("{}:1", series_id));
+
+        // --- Metadata của edition được tạo từ metadata của series
+        let token = contract.nft_token(token_id).unwrap();
+        assert_eq!(token.owner_id, accounts(1).to_string());
+        assert_eq!(
+            token.metadata,
+            TokenMetadata {
+                title: Some("TOKEN TEST #1".to_string()),
+                issued_at: Some(1_000),
+                ..series_metadata
+            }
+        );
+
+        contract.nft_mint_edition(series_id, accounts(2).to_string());
+        assert_eq!(contract.nft_series(series_id).unwrap().minted, 2);
+        assert_eq!(contract.nft_total_supply(), U128(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Series is sold out")]
+    fn test_mint_edition_over_copies() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let series_id = contract.nft_create_series(
+            TokenMetadata {
+                copies: Some(1),
+                ..get_sample_metadata()
+            },
+            None,
+            None,
+        );
+
+        contract.nft_mint_edition(series_id, accounts(1).to_string());
+        contract.nft_mint_edition(series_id, accounts(1).to_string());
+    }
+
+    #[test]
+    fn test_minter_quota() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        contract.add_minter(accounts(1).to_string(), Some(2), None);
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(1))
+            .build());
+
+        contract.nft_mint(
+            Some("zng_nft".to_string()),
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+
+        let minter = contract.get_minter(accounts(1).to_string()).unwrap();
+        assert_eq!(minter.minted, 1);
+        assert_eq!(minter.remaining, Some(1));
+        assert_eq!(contract.get_minters(None, Some(10)).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Minter quota exceeded, remaining 1")]
+    fn test_minter_quota_exceeded() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        contract.add_minter(accounts(1).to_string(), Some(1), None);
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 2)
+            .predecessor_account_id(accounts(1))
+            .build());
+
+        let tokens = vec!["zng_nft_1", "zng_nft_2"]
+            .into_iter()
+            .map(|token_id| MintArgs {
+                token_id: Some(token_id.to_string()),
+                metadata: get_sample_metadata(),
+                receiver_id: accounts(1).to_string(),
+                perpetual_royalties: None,
+                soulbound: None,
+            })
+            .collect();
+        contract.nft_batch_mint(tokens);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only authorized minters can mint")]
+    fn test_mint_not_minter() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(1))
+            .build());
+
+        contract.nft_mint(
+            Some("zng_nft".to_string()),
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Minter authorization has expired")]
+    fn test_mint_expired_minter() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        contract.add_minter(accounts(1).to_string(), None, Some(1_000));
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .predecessor_account_id(accounts(1))
+            .block_timestamp(1_000)
+            .build());
+
+        contract.nft_mint(
+            Some("zng_nft".to_string()),
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn test_remove_minter_refunds_storage() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let before_storage_usage = env::storage_usage();
+        contract.add_minter(accounts(1).to_string(), Some(2), None);
+        let minter_storage = env::storage_usage() - before_storage_usage;
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        contract.remove_minter(accounts(1).to_string());
+
+        assert!(contract.get_minter(accounts(1).to_string()).is_none());
+        assert_eq!(
+            transferred_to(&accounts(0).to_string()),
+            minter_storage as Balance * env::storage_byte_cost()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Required attached deposit of at least 1 yoctoNear")]
+    fn test_add_minter_without_deposit() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+
+        contract.add_minter(accounts(1).to_string(), None, None);
+    }
+
+    #[test]
+    fn test_transfer_ownership() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context.attached_deposit(1).build());
+        contract.propose_owner(accounts(1).to_string());
+
+        // --- Owner chỉ thay đổi sau khi owner mới accept
+        assert_eq!(contract.get_owner(), accounts(0).to_string());
+        assert_eq!(contract.get_pending_owner(), Some(accounts(1).to_string()));
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.accept_owner();
+
+        assert_eq!(contract.get_owner(), accounts(1).to_string());
+        assert_eq!(contract.get_pending_owner(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only pending owner can accept ownership")]
+    fn test_accept_ownership_not_pending_owner() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context.attached_deposit(1).build());
+        contract.propose_owner(accounts(1).to_string());
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.accept_owner();
+    }
+
+    #[test]
+    fn test_migrate_from_v1() {
+        let context = get_context(false);
+        testing_env!(context.build());
+
+        // --- State của bản deploy đầu tiên (chưa có versioning), có 1 token
+        let token_id = "zng_nft".to_string();
+        let mut state = ContractV1 {
+            owner_id: accounts(0).to_string(),
+            tokens_per_owner: LookupMap::new(StorageKey::TokenPerOwnerKey.try_to_vec().unwrap()),
+            tokens_by_id: LookupMap::new(StorageKey::TokenByIdKey.try_to_vec().unwrap()),
+            token_metadata_by_id: UnorderedMap::new(
+                StorageKey::TokenMetadataByIdKey.try_to_vec().unwrap(),
+            ),
+            metadata: LazyOption::new(StorageKey::ContractMetadataKey.try_to_vec().unwrap(), None),
+        };
+        state.tokens_by_id.insert(
+            &token_id,
+            &Token {
+                owner_id: accounts(1).to_string(),
+                approved_account_ids: HashMap::default(),
+                next_approval_id: 0,
+                royalty: HashMap::default(),
+            },
+        );
+        state
+            .token_metadata_by_id
+            .insert(&token_id, &get_sample_metadata());
+        env::state_write(&state);
+
+        let contract = Contract::migrate();
+
+        assert_eq!(contract.version(), STATE_VERSION);
+        assert_eq!(contract.get_owner(), accounts(0).to_string());
+        let token = contract.nft_token(token_id).unwrap();
+        assert_eq!(token.owner_id, accounts(1).to_string());
+        assert_eq!(token.metadata, get_sample_metadata());
+    }
+
+    #[test]
+    fn test_migrate_current_version() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let token_id = "zng_nft".to_string();
+        contract.nft_mint(
+            Some(token_id.clone()),
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+        env::state_write(&contract);
+
+        // --- Deploy lại code với cùng layout -> State được giữ nguyên
+        let contract = Contract::migrate();
+
+        assert_eq!(contract.version(), STATE_VERSION);
+        assert_eq!(contract.get_owner(), accounts(0).to_string());
+        let token = contract.nft_token(token_id).unwrap();
+        assert_eq!(token.owner_id, accounts(1).to_string());
+        assert_eq!(token.metadata, get_sample_metadata());
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown state version 3")]
+    fn test_migrate_unknown_version() {
+        let context = get_context(false);
+        testing_env!(context.build());
+
+        let contract = Contract::new_default_metadata(accounts(0).to_string());
+        env::state_write(&contract);
+        env::storage_write(STATE_VERSION_KEY, &3u16.try_to_vec().unwrap());
+
+        Contract::migrate();
+    }
+
+    #[test]
+    #[should_panic(expected = "Only contract owner can call this method")]
+    fn test_upgrade_not_owner() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+
+        contract.upgrade();
+    }
+
+    #[test]
+    fn test_update_token_metadata() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let token_id = "zng_nft".to_string();
+        contract.nft_mint(
+            Some(token_id.clone()),
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .block_timestamp(2_000_000_000)
+            .build());
+
+        let new_metadata = TokenMetadata {
+            description: Some("New description".to_string()),
+            ..get_sample_metadata()
+        };
+        contract.nft_metadata_update(token_id.clone(), new_metadata.clone(), None);
+
+        // --- Metadata mới được lưu lại cùng updated_at
+        assert_eq!(
+            contract.nft_token(token_id).unwrap().metadata,
+            TokenMetadata {
+                updated_at: Some(2_000),
+                ..new_metadata
+            }
+        );
+    }
+
+    #[test]
+    fn test_update_token_metadata_storage() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.add_minter(accounts(2).to_string(), None, None);
+        contract.storage_deposit(None, None);
+        let available = contract
+            .storage_balance_of(accounts(0).to_string())
+            .unwrap()
+            .available;
+
+        // --- accounts(2) mint hộ accounts(1) -> accounts(2) là người trả phí lưu trữ của token
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(2))
+            .build());
+        let token_id = contract.nft_mint(
+            None,
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+
+        // --- Metadata dài hơn, chỉ đính kèm 1 yoctoNEAR -> Phí tăng thêm trừ vào số dư storage đã nạp
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.nft_metadata_update(
+            token_id.clone(),
+            TokenMetadata {
+                description: Some("A much longer description than before".to_string()),
+                ..get_sample_metadata()
+            },
+            None,
+        );
+        assert!(
+            contract
+                .storage_balance_of(accounts(0).to_string())
+                .unwrap()
+                .available
+                .0
+                < available.0
+        );
+
+        // --- Metadata ngắn lại nhưng vẫn lớn hơn lúc mint -> Phần owner đã trả refund lại cho owner
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        contract.nft_metadata_update(token_id.clone(), get_sample_metadata(), None);
+        assert_eq!(transferred_to(&accounts(2).to_string()), 0);
+        assert!(transferred_to(&accounts(0).to_string()) > 1);
+        assert!(contract.token_metadata_growth.get(&token_id).unwrap() > 0);
+
+        // --- Metadata nhỏ hơn lúc mint -> Phần nhỏ hơn refund cho người trả phí lưu trữ của token
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        contract.nft_metadata_update(
+            token_id.clone(),
+            TokenMetadata {
+                description: None,
+                ..get_sample_metadata()
+            },
+            None,
+        );
+        assert!(transferred_to(&accounts(2).to_string()) > 0);
+        assert!(transferred_to(&accounts(0).to_string()) > 1);
+        assert!(contract.token_metadata_growth.get(&token_id).is_none());
+    }
+
+    #[test]
+    fn test_burn_refunds_metadata_growth() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.add_minter(accounts(1).to_string(), None, None);
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(1))
+            .build());
+        let token_id = contract.nft_mint(
+            None,
+            get_sample_metadata(),
+            accounts(1).to_string(),
+            None,
+            None,
+        );
+
+        // --- Owner của contract trả phí phần metadata tăng thêm
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+        let before_storage_usage = env::storage_usage();
+        contract.nft_metadata_update(
+            token_id.clone(),
+            TokenMetadata {
+                description: Some("A much longer description than before".to_string()),
+                ..get_sample_metadata()
+            },
+            None,
+        );
+        let growth_storage = env::storage_usage() - before_storage_usage;
+
+        // --- Burn -> Phần tăng thêm refund cho owner của contract, phần còn lại cho owner của token
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(1))
+            .build());
+        contract.nft_burn(token_id.clone(), None);
+
+        assert_eq!(
+            transferred_to(&accounts(0).to_string()),
+            Balance::from(growth_storage) * env::storage_byte_cost()
+        );
+        assert!(transferred_to(&accounts(1).to_string()) > 0);
+        assert!(contract.token_metadata_growth.get(&token_id).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only contract owner can call this method")]
+    fn test_update_contract_metadata_not_owner() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(1))
+            .build());
+
+        let metadata = contract.nft_metadata();
+        contract.nft_contract_metadata_update(metadata, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Metadata media_hash is required when media is set")]
+    fn test_mint_media_without_hash() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        contract.nft_mint(
+            Some("zng_nft".to_string()),
+            TokenMetadata {
+                media: Some("https://example.com/nft.png".to_string()),
+                ..get_sample_metadata()
+            },
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Metadata starts_at must not be after expires_at")]
+    fn test_mint_invalid_validity_window() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        contract.nft_mint(
+            Some("zng_nft".to_string()),
+            TokenMetadata {
+                starts_at: Some(2_000),
+                expires_at: Some(1_000),
+                ..get_sample_metadata()
+            },
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Total royalty 6000 exceeds the maximum of 5000")]
+    fn test_mint_royalty_over_max() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let mut perpetual_royalties = HashMap::new();
+        perpetual_royalties.insert(accounts(1).to_string(), 3_000);
+        perpetual_royalties.insert(accounts(2).to_string(), 3_000);
+        contract.nft_mint(
+            Some("zng_nft".to_string()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            Some(perpetual_royalties),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_default_royalty() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let mut default_royalty = HashMap::new();
+        default_royalty.insert(accounts(1).to_string(), 1_000);
+        contract.set_default_royalty(default_royalty.clone());
+
+        // --- Không truyền perpetual_royalties -> Dùng royalty mặc định
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST * 2)
+            .build());
+        let default_id = contract.nft_mint(
+            None,
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            None,
+            None,
+        );
+        assert_eq!(contract.nft_royalty(default_id.clone()), default_royalty);
+
+        // --- Truyền perpetual_royalties (kể cả rỗng) -> Ghi đè royalty mặc định
+        let override_id = contract.nft_mint(
+            None,
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            Some(HashMap::new()),
+            None,
+        );
+        assert!(contract.nft_royalty(override_id).is_empty());
+
+        // --- Đổi royalty mặc định không ảnh hưởng token đã mint
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(1)
+            .build());
+        let mut new_default_royalty = HashMap::new();
+        new_default_royalty.insert(accounts(2).to_string(), 500);
+        contract.set_default_royalty(new_default_royalty.clone());
+        assert_eq!(contract.nft_royalty(default_id), default_royalty);
+        assert_eq!(contract.get_default_royalty(), new_default_royalty);
+    }
+
+    #[test]
+    #[should_panic(expected = "Max royalty cannot be lower than the default royalty of 1000")]
+    fn test_max_royalty_below_default_royalty() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .attached_deposit(1)
+            .predecessor_account_id(accounts(0))
+            .build());
+        let mut default_royalty = HashMap::new();
+        default_royalty.insert(accounts(1).to_string(), 1_000);
+        contract.set_default_royalty(default_royalty);
+
+        // --- Cap thấp hơn royalty mặc định -> Các lần mint dùng royalty mặc định sẽ bị lỗi
+        contract.set_max_royalty(500);
+    }
+
+    #[test]
+    fn test_payout_large_balance() {
+        let mut context = get_context(false);
+        testing_env!(context.build());
+
+        let mut contract = Contract::new_default_metadata(accounts(0).to_string());
+
+        testing_env!(context
+            .storage_usage(env::storage_usage())
+            .attached_deposit(MINT_STORAGE_COST)
+            .predecessor_account_id(accounts(0))
+            .build());
+
+        let mut perpetual_royalties = HashMap::new();
+        perpetual_royalties.insert(accounts(1).to_string(), 1_000);
+        perpetual_royalties.insert(accounts(2).to_string(), 333);
+        contract.nft_mint(
+            Some("zng_nft".to_string()),
+            get_sample_metadata(),
+            accounts(0).to_string(),
+            Some(perpetual_royalties),
+            None,
+        );
+
+        // --- Balance lớn không làm tràn phép nhân, tổng payout luôn bằng balance
+        let payout = contract
+            .nft_payout("zng_nft".to_string(), U128(u128::MAX), Some(3))
+            .payout;
+        assert_eq!(payout[&accounts(1).to_string()], U128(u128::MAX / 10));
+        let total = payout
+            .values()
+            .fold(0u128, |total, amount| total.checked_add(amount.0).unwrap());
+        assert_eq!(total, u128::MAX);
+    }
+
+    #[test]
+    fn test_resolve_transfer_receiver_keeps_token() {
+        let mut context = get_context(false);
+        let token_id = "zng_nft".to_string();
+        let (mut contract, approved_account_ids) = setup_transfer_call(&mut context, &token_id);
+
+        testing_env_with_promise_results(
+            context.attached_deposit(0).build(),
+            PromiseResult::Successful(b"false".to_vec()),
+        );
+        let is_transferred = contract.nft_resolve_transfer(
+            None,
+            accounts(0).to_string(),
+            accounts(1).to_string(),
+            token_id.clone(),
+            approved_account_ids,
+            None,
+        );
+
+        assert!(is_transferred);
+        let token = contract.nft_token(token_id).unwrap();
+        assert_eq!(token.owner_id, accounts(1).to_string());
+        assert!(token.approved_account_ids.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_transfer_receiver_returns_token() {
+        let mut context = get_context(false);
+        let token_id = "zng_nft".to_string();
+        let (mut contract, approved_account_ids) = setup_transfer_call(&mut context, &token_id);
+
+        testing_env_with_promise_results(
+            context.attached_deposit(0).build(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        let is_transferred = contract.nft_resolve_transfer(
+            None,
+            accounts(0).to_string(),
+            accounts(1).to_string(),
+            token_id.clone(),
+            approved_account_ids.clone(),
+            None,
+        );
+
+        // --- Token được trả lại cho owner cũ cùng với các approvals
+        assert!(!is_transferred);
+        let token = contract.nft_token(token_id.clone()).unwrap();
+        assert_eq!(token.owner_id, accounts(0).to_string());
+        assert_eq!(token.approved_account_ids, approved_account_ids);
+        assert_eq!(
+            contract.nft_supply_for_owner(accounts(1).to_string()),
+            U128(0)
+        );
+        // --- Entry của lần transfer bị rollback đã bị huỷ, phí lưu trữ refund cho sender
+        let history = contract.nft_token_history(token_id, None, Some(10));
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].kind, TokenHistoryKind::Mint);
+        assert!(transferred_to(&accounts(0).to_string()) > 0);
+    }
+
+    #[test]
+    fn test_resolve_transfer_receiver_failed() {
+        let mut context = get_context(false);
+        let token_id = "zng_nft".to_string();
+        let (mut contract, approved_account_ids) = setup_transfer_call(&mut context, &token_id);
+
+        testing_env_with_promise_results(
+            context.attached_deposit(0).build(),
+            PromiseResult::Failed,
+        );
+        let is_transferred = contract.nft_resolve_transfer(
+            None,
+            accounts(0).to_string(),
+            accounts(1).to_string(),
+            token_id.clone(),
+            approved_account_ids,
+            None,
+        );
+
+        assert!(!is_transferred);
+        assert_eq!(
+            contract.nft_token(token_id).unwrap().owner_id,
+            accounts(0).to_string()
+        );
+    }
+
+    #[test]
+    fn test_resolve_transfer_token_moved_by_receiver() {
+        let mut context = get_context(false);
+        let token_id = "zng_nft".to_string();
+        let (mut contract, approved_account_ids) = setup_transfer_call(&mut context, &token_id);
+
+        // --- Receiver chuyển token cho accounts(3) trước khi resolve
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.nft_transfer(accounts(3).to_string(), token_id.clone(), 0, None);
+
+        testing_env_with_promise_results(
+            context
+                .predecessor_account_id(accounts(0))
+                .attached_deposit(0)
+                .build(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        let is_transferred = contract.nft_resolve_transfer(
+            None,
+            accounts(0).to_string(),
+            accounts(1).to_string(),
+            token_id.clone(),
+            approved_account_ids,
+            None,
+        );
+
+        assert!(is_transferred);
+        assert_eq!(
+            contract.nft_token(token_id).unwrap().owner_id,
+            accounts(3).to_string()
+        );
+    }
+
+    #[test]
+    fn test_resolve_transfer_token_burned() {
+        let mut context = get_context(false);
+        let token_id = "zng_nft".to_string();
+        let (mut contract, approved_account_ids) = setup_transfer_call(&mut context, &token_id);
+
+        // --- Receiver burn token trước khi resolve
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.nft_burn(token_id.clone(), None);
+
+        testing_env_with_promise_results(
+            context
+                .predecessor_account_id(accounts(0))
+                .attached_deposit(0)
+                .build(),
+            PromiseResult::Successful(b"true".to_vec()),
+        );
+        let is_transferred = contract.nft_resolve_transfer(
+            None,
+            accounts(0).to_string(),
+            accounts(1).to_string(),
+            token_id.clone(),
+            approved_account_ids,
+            None,
+        );
+
+        // --- Không trả lại được token, phí approve refund cho owner cũ
+        assert!(is_transferred);
+        assert!(contract.nft_token(token_id).is_none());
+        assert_eq!(
+            transferred_to(&accounts(0).to_string()),
+            Balance::from(bytes_for_approved_account_id(&accounts(2).to_string()))
+                * env::storage_byte_cost()
+        );
     }
 }
@@ -18,6 +18,10 @@ pub struct JsonToken {
     pub metadata: TokenMetadata,
     pub approved_account_ids: HashMap<AccountId, u64>,
     pub royalty: HashMap<AccountId, u32>,
+    // Token soulbound không thể transfer -> Market có thể ẩn các token này
+    pub soulbound: bool,
+    // Token có đang trong thời gian hiệu lực (starts_at, expires_at) hay không
+    pub is_valid: bool,
 }
 
 // Các metadata theo chuẩn NEP-177 của NEAR - Metadata
@@ -34,7 +38,9 @@ pub struct NFTContractMetadata {
     pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, PartialEq, Debug)]
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Default, PartialEq, Debug,
+)]
 #[serde(crate = "near_sdk::serde")]
 pub struct TokenMetadata {
     pub title: Option<String>, // ex. "Arch Nemesis: Mail Carrier" or "Parcel #5055"
@@ -51,6 +57,107 @@ pub struct TokenMetadata {
     pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
 }
 
+// Giới hạn độ dài các trường metadata
+const MAX_NAME_LENGTH: usize = 256;
+const MAX_SYMBOL_LENGTH: usize = 32;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+const MAX_URL_LENGTH: usize = 1024;
+const MAX_ICON_LENGTH: usize = 16384; // Icon là data URL nên cho phép dài hơn URL thường
+const MAX_EXTRA_LENGTH: usize = 4096;
+// Độ dài của sha256 hash
+const HASH_LENGTH: usize = 32;
+
+impl NFTContractMetadata {
+    // Kiểm tra metadata của contract theo chuẩn NEP-177
+    pub fn assert_valid(&self) {
+        assert!(!self.spec.is_empty(), "Metadata spec is required");
+        assert_max_length("name", Some(&self.name), MAX_NAME_LENGTH);
+        assert!(!self.name.is_empty(), "Metadata name is required");
+        assert_max_length("symbol", Some(&self.symbol), MAX_SYMBOL_LENGTH);
+        assert!(!self.symbol.is_empty(), "Metadata symbol is required");
+        assert_max_length("icon", self.icon.as_ref(), MAX_ICON_LENGTH);
+        assert_max_length("base_uri", self.base_uri.as_ref(), MAX_URL_LENGTH);
+        assert_max_length("reference", self.reference.as_ref(), MAX_URL_LENGTH);
+        assert_hash_pair(
+            "reference",
+            self.reference.as_ref(),
+            self.reference_hash.as_ref(),
+        );
+    }
+}
+
+impl TokenMetadata {
+    // Kiểm tra metadata của token theo chuẩn NEP-177
+    pub fn assert_valid(&self) {
+        assert_max_length("title", self.title.as_ref(), MAX_NAME_LENGTH);
+        assert_max_length(
+            "description",
+            self.description.as_ref(),
+            MAX_DESCRIPTION_LENGTH,
+        );
+        assert_max_length("media", self.media.as_ref(), MAX_URL_LENGTH);
+        assert_max_length("extra", self.extra.as_ref(), MAX_EXTRA_LENGTH);
+        assert_max_length("reference", self.reference.as_ref(), MAX_URL_LENGTH);
+        assert_hash_pair("media", self.media.as_ref(), self.media_hash.as_ref());
+        assert_hash_pair(
+            "reference",
+            self.reference.as_ref(),
+            self.reference_hash.as_ref(),
+        );
+
+        if let Some(copies) = self.copies {
+            assert!(copies > 0, "Metadata copies must be greater than 0");
+        }
+        if let (Some(starts_at), Some(expires_at)) = (self.starts_at, self.expires_at) {
+            assert!(
+                starts_at <= expires_at,
+                "Metadata starts_at must not be after expires_at"
+            );
+        }
+    }
+
+    // Token đã hết hạn tại thời điểm now (milliseconds) chưa?
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    // Token có hiệu lực tại thời điểm now (milliseconds): đã tới starts_at và chưa tới expires_at
+    pub fn is_valid(&self, now: u64) -> bool {
+        self.starts_at.is_none_or(|starts_at| now >= starts_at) && !self.is_expired(now)
+    }
+}
+
+fn assert_max_length(field: &str, value: Option<&String>, max_length: usize) {
+    if let Some(value) = value {
+        assert!(
+            value.len() <= max_length,
+            "Metadata {} exceeds {} bytes",
+            field,
+            max_length
+        );
+    }
+}
+
+// `<field>_hash` bắt buộc phải có khi có `<field>` và phải là sha256 hash (32 bytes)
+fn assert_hash_pair(field: &str, value: Option<&String>, hash: Option<&Base64VecU8>) {
+    match (value, hash) {
+        (Some(_), Some(hash)) => assert_eq!(
+            hash.0.len(),
+            HASH_LENGTH,
+            "Metadata {}_hash must be a {}-byte sha256 hash",
+            field,
+            HASH_LENGTH
+        ),
+        (Some(_), None) => env::panic(
+            format!("Metadata {}_hash is required when {} is set", field, field).as_bytes(),
+        ),
+        (None, Some(_)) => {
+            env::panic(format!("Metadata {}_hash is set without {}", field, field).as_bytes())
+        }
+        (None, None) => {}
+    }
+}
+
 pub trait NonFungibleTokenMetada {
     fn nft_metadata(&self) -> NFTContractMetadata;
 }
@@ -61,3 +168,94 @@ impl NonFungibleTokenMetada for Contract {
         self.metadata.get().unwrap()
     }
 }
+
+#[near_bindgen]
+impl Contract {
+    /**
+     * Cập nhật metadata của token (chỉ owner của contract)
+     * - Edition của series dùng chung metadata của series -> Không được cập nhật riêng
+     * - Đã commit reveal -> Metadata của mọi token chỉ được cập nhật qua nft_reveal (kèm Merkle proof)
+     * - Cập nhật updated_at, tính thêm phí storage tăng thêm (từ deposit hoặc số dư storage đã nạp)
+     * - Metadata nhỏ lại: phần owner đã trả khi cập nhật trước đó refund cho owner,
+     *   phần nhỏ hơn lúc mint refund cho người trả phí lưu trữ của token (mặc định là owner của token)
+     * - Log event nft_metadata_update để indexer cập nhật lại metadata
+     */
+    #[payable]
+    pub fn nft_metadata_update(
+        &mut self,
+        token_id: TokenId,
+        metadata: TokenMetadata,
+        memo: Option<String>,
+    ) {
+        assert_at_least_one_yocto();
+        self.assert_owner();
+        let token = self.tokens_by_id.get(&token_id).expect("Not found token");
+        assert!(
+            parse_edition_token_id(&token_id).is_none(),
+            "Edition metadata is shared by its series"
+        );
+        assert!(
+            self.reveal_root.is_none(),
+            "Metadata is locked by the reveal commitment, use nft_reveal"
+        );
+        metadata.assert_valid();
+        let storage_payer = self.internal_storage_payer(&token_id, &token.owner_id);
+
+        let before_storage_usage = env::storage_usage();
+
+        let released = self.internal_replace_token_metadata(
+            &token_id,
+            &TokenMetadata {
+                updated_at: Some(env::block_timestamp() / 1_000_000),
+                ..metadata
+            },
+            storage_payer,
+        );
+
+        // NFT METADATA UPDATE LOG
+        let nft_metadata_update_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.1.0".to_string(),
+            event: EventLogVariant::NftMetadataUpdate(vec![NftMetadataUpdateLog {
+                token_ids: vec![token_id],
+                memo,
+            }]),
+        };
+        env::log(nft_metadata_update_log.to_string().as_bytes());
+
+        // Phần đã refund cho người trả phí lưu trữ không tính vào thay đổi storage của owner
+        self.internal_charge_storage_change(before_storage_usage, env::storage_usage() + released);
+    }
+
+    /**
+     * Cập nhật metadata của contract (chỉ owner của contract)
+     * - Tính thêm (từ deposit hoặc số dư storage đã nạp) hoặc refund phí storage chênh lệch
+     * - Log event contract_metadata_update
+     */
+    #[payable]
+    pub fn nft_contract_metadata_update(
+        &mut self,
+        metadata: NFTContractMetadata,
+        memo: Option<String>,
+    ) {
+        assert_at_least_one_yocto();
+        self.assert_owner();
+        metadata.assert_valid();
+
+        let before_storage_usage = env::storage_usage();
+
+        self.metadata.set(&metadata);
+
+        // CONTRACT METADATA UPDATE LOG
+        let contract_metadata_update_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.1.0".to_string(),
+            event: EventLogVariant::ContractMetadataUpdate(vec![ContractMetadataUpdateLog {
+                memo,
+            }]),
+        };
+        env::log(contract_metadata_update_log.to_string().as_bytes());
+
+        self.internal_charge_storage_change(before_storage_usage, env::storage_usage());
+    }
+}
@@ -1,78 +1,189 @@
 // Hàm mint NFT
 
 use crate::*;
+use std::collections::HashSet;
+
+// Độ dài tối đa của prefix dùng cho token_id tự sinh
+const MAX_TOKEN_ID_PREFIX_LENGTH: usize = 64;
+
+// Thông tin của 1 token trong nft_batch_mint
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintArgs {
+    pub token_id: Option<TokenId>, // None -> Contract tự sinh token_id
+
+    pub metadata: TokenMetadata,
+    pub receiver_id: AccountId,
+    pub perpetual_royalties: Option<HashMap<AccountId, u32>>,
+    pub soulbound: Option<bool>,
+}
 
 #[near_bindgen]
 impl Contract {
     /**
+     * - Chỉ owner của contract hoặc minter (còn quota, chưa hết hạn) mới được mint
      * - Yêu cầu user nạp tiền để cover phí lưu trữ
      * - Thêm token vào tokens_by_id
      * - Thêm token metadata
      * - Thêm token vào danh sách sở hữu bởi owner
+     * - soulbound = true -> Token không thể transfer, người mint là issuer (có quyền burn token)
+     * - token_id = None -> Contract tự sinh token_id dạng `<prefix><số thứ tự>`, tránh trùng token_id khi mint đồng thời
+     * - perpetual_royalties = None -> Dùng royalty mặc định của contract (xem set_default_royalty)
      * - Refund lại NEAR user deposit thừa
+     * - Return token_id của token vừa mint
      */
     #[payable]
     pub fn nft_mint(
         &mut self,
-        token_id: TokenId,
+        token_id: Option<TokenId>,
         metadata: TokenMetadata,
         receiver_id: AccountId,
         perpetual_royalties: Option<HashMap<AccountId, u32>>,
-    ) {
-        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
-
-        let mut royalty = HashMap::new();
-
-        // if perpetual royalties were passed into the function:
-        if let Some(perpetual_royalties) = perpetual_royalties {
-            //make sure that the length of the perpetual royalties is below 7 since we won't have enough GAS to pay out that many people
-            assert!(
-                perpetual_royalties.len() < 7,
-                "Cannot add more than 6 perpetual royalty amounts"
-            );
-
-            //iterate through the perpetual royalties and insert the account and amount in the royalty map
-            for (account, amount) in perpetual_royalties {
-                royalty.insert(account, amount);
-            }
-        }
+        soulbound: Option<bool>,
+    ) -> TokenId {
+        self.assert_mint_not_paused();
+        let token_id = token_id.unwrap_or_else(|| self.internal_next_token_id());
+        assert_not_edition_token_id(&token_id);
+        let minter_id = env::predecessor_account_id();
+        self.internal_use_mint_quota(&minter_id, 1);
 
-        let token = Token {
-            owner_id: receiver_id,
-            approved_account_ids: HashMap::default(),
-            next_approval_id: 0,
+        let royalty = self.internal_royalty_for_mint(perpetual_royalties);
+        let soulbound_issuer = soulbound.unwrap_or(false).then_some(&minter_id);
+        // Luợng data storage sử dụng của token (kể cả entry mint trong lịch sử)
+        let storage_usage = self.internal_mint(
+            &token_id,
+            &metadata,
+            &receiver_id,
             royalty,
-        };
-
-        // Nếu token_id đã tồn tại trong list tokens_by_id thì báo lỗi
-        // Trong LookupMap, nếu key chưa tồn tại trong map -> Hàm insert return None
-        assert!(
-            self.tokens_by_id.insert(&token_id, &token).is_none(),
-            "Token already exists"
+            soulbound_issuer,
+            &minter_id,
         );
 
-        // Thêm token metadata
-        self.token_metadata_by_id.insert(&token_id, &metadata);
-
-        // Thêm token vào danh sách sở hữu bởi owner
-        self.internal_add_token_to_owner(&token_id, &token.owner_id);
-
         // NFT MINT LOG
         let nft_mint_log: EventLog = EventLog {
             standard: "nep171".to_string(),
             version: "1.0.0".to_string(),
             event: EventLogVariant::NftMint(vec![NftMintLog {
-                owner_id: token.owner_id.to_string(),
+                owner_id: receiver_id.to_string(),
                 token_ids: vec![token_id.to_string()],
                 memo: None,
             }]),
         };
         env::log(&nft_mint_log.to_string().as_bytes());
 
-        // Luợng data storage sử dụng = after_storage_usage - before_storage_usage
-        let after_storage_usage = env::storage_usage();
         // Refund NEAR
-        refund_deposit(after_storage_usage - before_storage_usage);
+        self.internal_charge_storage(storage_usage);
+
+        token_id
+    }
+
+    /**
+     * Mint nhiều token trong 1 transaction
+     * - Nếu có token_id đã tồn tại (hoặc bị trùng trong batch) -> Cả batch đều thất bại
+     * - Token không có token_id sẽ được contract tự sinh token_id
+     * - Tính phí storage cho cả batch
+     * - Log 1 NftMintLog cho mỗi receiver, gom các token_ids của receiver đó
+     * - Return token_ids theo thứ tự của tokens
+     */
+    #[payable]
+    pub fn nft_batch_mint(&mut self, tokens: Vec<MintArgs>) -> Vec<TokenId> {
+        self.assert_mint_not_paused();
+        assert!(!tokens.is_empty(), "Batch must contain at least one token");
+        let minter_id = env::predecessor_account_id();
+        self.internal_use_mint_quota(&minter_id, tokens.len() as u64);
+
+        // token_id tự sinh bỏ qua các token_id được truyền vào trong cùng batch
+        let explicit_token_ids: HashSet<TokenId> = tokens
+            .iter()
+            .filter_map(|args| args.token_id.clone())
+            .collect();
+        let token_ids: Vec<TokenId> = tokens
+            .iter()
+            .map(|args| {
+                args.token_id.clone().unwrap_or_else(|| loop {
+                    let token_id = self.internal_next_token_id();
+                    if !explicit_token_ids.contains(&token_id) {
+                        break token_id;
+                    }
+                })
+            })
+            .collect();
+
+        // Kiểm tra toàn bộ token_id trước khi mint để đảm bảo batch thất bại trọn vẹn
+        let mut batch_token_ids = HashSet::new();
+        for token_id in token_ids.iter() {
+            assert_not_edition_token_id(token_id);
+            assert!(
+                batch_token_ids.insert(token_id),
+                "Token {} is duplicated in batch",
+                token_id
+            );
+            assert!(
+                self.tokens_by_id.get(token_id).is_none(),
+                "Token {} already exists",
+                token_id
+            );
+        }
+
+        let mut storage_usage = 0;
+        let mut mint_logs: Vec<NftMintLog> = Vec::new();
+        for (args, token_id) in tokens.into_iter().zip(token_ids.iter()) {
+            let royalty = self.internal_royalty_for_mint(args.perpetual_royalties);
+            let soulbound_issuer = args.soulbound.unwrap_or(false).then_some(&minter_id);
+            storage_usage += self.internal_mint(
+                token_id,
+                &args.metadata,
+                &args.receiver_id,
+                royalty,
+                soulbound_issuer,
+                &minter_id,
+            );
+
+            // Gom token_ids theo receiver
+            match mint_logs
+                .iter_mut()
+                .find(|log| log.owner_id == args.receiver_id)
+            {
+                Some(log) => log.token_ids.push(token_id.clone()),
+                None => mint_logs.push(NftMintLog {
+                    owner_id: args.receiver_id,
+                    token_ids: vec![token_id.clone()],
+                    memo: None,
+                }),
+            }
+        }
+
+        // NFT MINT LOG
+        let nft_mint_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftMint(mint_logs),
+        };
+        env::log(nft_mint_log.to_string().as_bytes());
+
+        // Refund NEAR
+        self.internal_charge_storage(storage_usage);
+
+        token_ids
+    }
+
+    // Cập nhật prefix cho token_id tự sinh, chỉ áp dụng cho các token mint sau này
+    #[payable]
+    pub fn set_token_id_prefix(&mut self, prefix: String) {
+        assert_one_yocto();
+        self.assert_owner();
+        assert_not_edition_token_id(&prefix);
+        assert!(
+            prefix.len() <= MAX_TOKEN_ID_PREFIX_LENGTH,
+            "Token id prefix exceeds {} bytes",
+            MAX_TOKEN_ID_PREFIX_LENGTH
+        );
+
+        self.token_id_prefix = prefix;
+    }
+
+    pub fn get_token_id_prefix(&self) -> String {
+        self.token_id_prefix.clone()
     }
 
     // Lấy thông tin token dưới dạng JsonToken
@@ -80,14 +191,18 @@ impl Contract {
         let token = self.tokens_by_id.get(&token_id);
 
         if let Some(token) = token {
-            let metadata = self.token_metadata_by_id.get(&token_id).unwrap();
+            let metadata = self.internal_token_metadata(&token_id);
+            let soulbound = self.soulbound_tokens.contains_key(&token_id);
+            let is_valid = metadata.is_valid(env::block_timestamp() / 1_000_000);
 
             Some(JsonToken {
                 owner_id: token.owner_id,
                 token_id,
                 metadata,
                 approved_account_ids: token.approved_account_ids,
-                royalty: token.royalty
+                royalty: token.royalty,
+                soulbound,
+                is_valid,
             })
         } else {
             None
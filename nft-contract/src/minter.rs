@@ -0,0 +1,127 @@
+/**
+ * Quản lý danh sách các account được phép mint NFT
+ * Owner của contract luôn được phép mint, các account khác phải được owner thêm vào danh sách minters
+ * Mỗi minter có thể bị giới hạn số lượng token được mint (quota) và thời hạn (expires_at)
+ */
+use crate::*;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Minter {
+    // Số lượng token tối đa được mint (None -> Không giới hạn)
+    pub quota: Option<u64>,
+    // Số lượng token đã mint
+    pub minted: u64,
+    // Thời điểm hết quyền mint, tính theo block timestamp (nanoseconds). None -> Không hết hạn
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonMinter {
+    pub account_id: AccountId,
+    pub quota: Option<u64>,
+    pub minted: u64,
+    // Số lượng token còn được mint (None -> Không giới hạn)
+    pub remaining: Option<u64>,
+    pub expires_at: Option<u64>,
+}
+
+#[near_bindgen]
+impl Contract {
+    // Thêm minter mới hoặc cập nhật quota, expires_at của minter đã có
+    // Note: Số lượng token đã mint của minter được giữ nguyên khi cập nhật
+    // Tính thêm (từ deposit hoặc số dư storage đã nạp) hoặc refund phí storage chênh lệch cho owner
+    #[payable]
+    pub fn add_minter(
+        &mut self,
+        account_id: AccountId,
+        quota: Option<u64>,
+        expires_at: Option<u64>,
+    ) {
+        assert_at_least_one_yocto();
+        self.assert_owner();
+
+        let before_storage_usage = env::storage_usage();
+
+        let minted = self
+            .minters
+            .get(&account_id)
+            .map(|minter| minter.minted)
+            .unwrap_or(0);
+        self.minters.insert(
+            &account_id,
+            &Minter {
+                quota,
+                minted,
+                expires_at,
+            },
+        );
+
+        self.internal_charge_storage_change(before_storage_usage, env::storage_usage());
+
+        // MINTER ADD LOG
+        let minter_log: EventLog = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::MinterAdd(vec![MinterLog {
+                account_id: account_id.to_string(),
+                quota,
+                expires_at,
+            }]),
+        };
+        env::log(minter_log.to_string().as_bytes());
+    }
+
+    // Xoá quyền mint của account_id, refund phí lưu trữ cho owner
+    #[payable]
+    pub fn remove_minter(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+
+        let before_storage_usage = env::storage_usage();
+
+        self.minters.remove(&account_id).expect("Not found minter");
+
+        refund_storage_released(
+            env::predecessor_account_id(),
+            before_storage_usage - env::storage_usage(),
+        );
+
+        // MINTER REMOVE LOG
+        let minter_log: EventLog = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::MinterRemove(vec![MinterLog {
+                account_id: account_id.to_string(),
+                quota: None,
+                expires_at: None,
+            }]),
+        };
+        env::log(minter_log.to_string().as_bytes());
+    }
+
+    // Lấy thông tin minter
+    pub fn get_minter(&self, account_id: AccountId) -> Option<JsonMinter> {
+        self.minters.get(&account_id).map(|minter| JsonMinter {
+            account_id,
+            quota: minter.quota,
+            minted: minter.minted,
+            remaining: minter
+                .quota
+                .map(|quota| quota.saturating_sub(minter.minted)),
+            expires_at: minter.expires_at,
+        })
+    }
+
+    // Lấy danh sách minters (có pagination)
+    pub fn get_minters(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonMinter> {
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        self.minters
+            .keys()
+            .skip(start as usize) // Pagination
+            .take(limit.unwrap_or(0) as usize) // Pagination
+            .map(|account_id| self.get_minter(account_id).unwrap())
+            .collect()
+    }
+}
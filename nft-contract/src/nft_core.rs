@@ -55,7 +55,7 @@ trait NonFungibleTokenResolver {
     ) -> bool;
 }
 
-trait NonFungibleTokenResolver {
+pub trait NonFungibleTokenResolver {
     fn nft_resolve_transfer(
         &mut self,
         authorized_id: Option<AccountId>,
@@ -79,14 +79,32 @@ impl NonFungibleTokenCore for Contract {
         approval_id: u64,
         memo: Option<String>,
     ) {
+        self.assert_transfers_not_paused();
         assert_one_yocto();
         let sender_id = env::predecessor_account_id();
 
-        let previous_token =
-            self.internal_transfer(&sender_id, &receiver_id, &token_id, Some(approval_id), memo);
+        let previous_token = self.internal_transfer(
+            &sender_id,
+            &receiver_id,
+            &token_id,
+            Some(approval_id),
+            memo.clone(),
+        );
+        // Ghi lại lịch sử transfer, phí lưu trữ do sender trả
+        self.internal_record_transfer(
+            &token_id,
+            &previous_token.owner_id,
+            &receiver_id,
+            memo,
+            None,
+        );
 
-        // Refund nếu deposit thừa
-        refund_approved_account_ids(sender_id, &previous_token.approved_account_ids);
+        // Refund phí approve cho owner cũ (sender có thể là account được approve hoặc operator)
+        self.internal_refund_previous_approvals(
+            &token_id,
+            previous_token.owner_id,
+            &previous_token.approved_account_ids,
+        );
     }
 
     #[payable]
@@ -98,11 +116,20 @@ impl NonFungibleTokenCore for Contract {
         approval_id: u64,
         msg: String,
     ) -> PromiseOrValue<bool> {
+        self.assert_transfers_not_paused();
         assert_one_yocto();
         let sender_id = env::predecessor_account_id();
 
         let previous_token =
             self.internal_transfer(&sender_id, &receiver_id, &token_id, Some(approval_id), memo.clone());
+        // Ghi lại lịch sử transfer, phí lưu trữ do sender trả (entry bị huỷ nếu transfer bị rollback)
+        self.internal_record_transfer(
+            &token_id,
+            &previous_token.owner_id,
+            &receiver_id,
+            memo.clone(),
+            None,
+        );
 
         let mut authorized_id = None;
         if sender_id != previous_token.owner_id {
@@ -140,6 +167,8 @@ impl NonFungibleTokenCore for Contract {
 #[near_bindgen]
 impl NonFungibleTokenResolver for Contract {
     // Xử lý call back của nft_on_transfer khi contract nhận gọi lại
+    // Return true nếu token đã được chuyển cho receiver_id, false nếu token được trả lại cho owner_id
+    #[private]
     fn nft_resolve_transfer(
         &mut self,
         authorized_id: Option<AccountId>,
@@ -149,23 +178,33 @@ impl NonFungibleTokenResolver for Contract {
         approved_account_ids: HashMap<AccountId, u64>,
         memo: Option<String>,
     ) -> bool {
-        if let PromiseResult::Successful(value) = env::promise_result(0) {
-            // Thành công, chỉ có 1 promise
-            if let Ok(is_rollback_token) = near_sdk::serde_json::from_slice::<bool>(&value) {
-                return is_rollback_token;
+        // Receiver return true -> Yêu cầu trả lại token cho owner cũ
+        // nft_on_transfer bị lỗi hoặc return không phải bool -> Cũng trả lại token
+        let must_return_token = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                near_sdk::serde_json::from_slice::<bool>(&value).unwrap_or(true)
             }
+            PromiseResult::Failed => true,
+        };
+
+        // Receiver giữ token -> Transfer thành công, refund phí approve cho owner cũ
+        if !must_return_token {
+            self.internal_refund_previous_approvals(&token_id, owner_id, &approved_account_ids);
+            return true;
         }
 
         // Xử lý các case không thể rollback lại được
         let mut token = if let Some(token) = self.tokens_by_id.get(&token_id) {
-            // Nếu người nhận ko phải là owner -> Không thực hiện được -> rollback
+            // Receiver đã chuyển token cho người khác -> Không trả lại được, transfer coi như thành công
             if token.owner_id != receiver_id {
-                refund_approved_account_ids(owner_id, &approved_account_ids);
+                self.internal_refund_previous_approvals(&token_id, owner_id, &approved_account_ids);
                 return true;
             }
             token
         } else {
-            // Nếu không tìm thấy token -> Không thực hiện được -> rollback
+            // Token đã bị burn -> Không trả lại được, transfer coi như thành công
+            // Phí approve do owner cũ trả khi approve -> Refund cho owner cũ (thời hạn của approval đã được xoá khi burn)
             refund_approved_account_ids(owner_id, &approved_account_ids);
             return true;
         };
@@ -181,10 +220,23 @@ impl NonFungibleTokenResolver for Contract {
         self.internal_remove_token_from_owner(&token_id, &receiver_id); // Xoá token của người vừa nhận
         self.internal_add_token_to_owner(&token_id, &owner_id); // Trả lại token cho owner cũ
 
+        // Huỷ entry lịch sử của lần transfer bị rollback, refund phí lưu trữ cho người đã thực hiện transfer
+        let storage_released = self.internal_pop_transfer_history(&token_id, &receiver_id);
+        refund_storage_released(
+            authorized_id.clone().unwrap_or_else(|| owner_id.clone()),
+            storage_released,
+        );
+
         // Lấy lại các giá trị của token
         token.owner_id = owner_id.clone();
 
-        refund_approved_account_ids(receiver_id.clone(), &token.approved_account_ids);
+        // Refund phí approve mà receiver đã tạo trong lúc giữ token, khôi phục approvals của owner cũ
+        // Thời hạn của approvals cũ vẫn được giữ nguyên (lưu theo approval id)
+        self.internal_refund_previous_approvals(
+            &token_id,
+            receiver_id.clone(),
+            &token.approved_account_ids,
+        );
         token.approved_account_ids = approved_account_ids;
 
         self.tokens_by_id.insert(&token_id, &token);
@@ -199,12 +251,28 @@ impl NonFungibleTokenResolver for Contract {
                 old_owner_id: receiver_id.to_string(),
                 new_owner_id: owner_id.to_string(),
                 token_ids: vec![token_id.to_string()],
-                memo
-            }])
+                memo,
+            }]),
         };
 
-        env::log(&nft_transfer_log.to_string().as_bytes());
+        env::log(nft_transfer_log.to_string().as_bytes());
 
-        false // Cho front-end biết là giao dịch thất bại -> Rollback toàn bộ data
+        false // Cho front-end biết là token đã được trả lại cho owner cũ
+    }
+}
+
+impl Contract {
+    // Refund phí approve (kể cả thời hạn của approval) của các approvals không còn dùng tới cho owner_id
+    // Chỉ owner mới được approve -> owner_id là người đã trả phí lưu trữ của các approvals
+    pub(crate) fn internal_refund_previous_approvals(
+        &mut self,
+        token_id: &TokenId,
+        owner_id: AccountId,
+        approved_account_ids: &HashMap<AccountId, u64>,
+    ) {
+        let storage_released =
+            self.internal_remove_approval_expirations(token_id, approved_account_ids.values());
+        refund_storage_released(owner_id.clone(), storage_released);
+        refund_approved_account_ids(owner_id, approved_account_ids);
     }
 }
@@ -0,0 +1,94 @@
+/**
+ * Tạm dừng contract khi có sự cố (ex. phát hiện bug trong transfer hoặc payout)
+ * - Owner của contract bật/tắt riêng từng chức năng: mint, transfers, approvals
+ * - Các hàm view vẫn hoạt động bình thường khi contract bị tạm dừng
+ */
+use crate::*;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Default, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseStatus {
+    pub mint: bool,
+    pub transfers: bool,
+    pub approvals: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    /**
+     * Bật/tắt tạm dừng cho từng chức năng, None -> Giữ nguyên trạng thái hiện tại
+     * - Log event contract_paused cho các chức năng vừa bị tạm dừng
+     * - Log event contract_unpaused cho các chức năng vừa được mở lại
+     */
+    #[payable]
+    pub fn set_paused(
+        &mut self,
+        mint: Option<bool>,
+        transfers: Option<bool>,
+        approvals: Option<bool>,
+    ) {
+        assert_one_yocto();
+        self.assert_owner();
+
+        let old_status = self.paused;
+        self.paused = PauseStatus {
+            mint: mint.unwrap_or(old_status.mint),
+            transfers: transfers.unwrap_or(old_status.transfers),
+            approvals: approvals.unwrap_or(old_status.approvals),
+        };
+
+        let changes = [
+            ("mint", old_status.mint, self.paused.mint),
+            ("transfers", old_status.transfers, self.paused.transfers),
+            ("approvals", old_status.approvals, self.paused.approvals),
+        ];
+        let paused: Vec<String> = changes
+            .iter()
+            .filter(|(_, old, new)| !old && *new)
+            .map(|(feature, _, _)| feature.to_string())
+            .collect();
+        let unpaused: Vec<String> = changes
+            .iter()
+            .filter(|(_, old, new)| *old && !new)
+            .map(|(feature, _, _)| feature.to_string())
+            .collect();
+
+        // CONTRACT PAUSED LOG
+        if !paused.is_empty() {
+            let pause_log: EventLog = EventLog {
+                standard: "zng-nft".to_string(),
+                version: "1.0.0".to_string(),
+                event: EventLogVariant::ContractPaused(vec![PauseLog { features: paused }]),
+            };
+            env::log(pause_log.to_string().as_bytes());
+        }
+
+        // CONTRACT UNPAUSED LOG
+        if !unpaused.is_empty() {
+            let unpause_log: EventLog = EventLog {
+                standard: "zng-nft".to_string(),
+                version: "1.0.0".to_string(),
+                event: EventLogVariant::ContractUnpaused(vec![PauseLog { features: unpaused }]),
+            };
+            env::log(unpause_log.to_string().as_bytes());
+        }
+    }
+
+    pub fn get_paused(&self) -> PauseStatus {
+        self.paused
+    }
+}
+
+impl Contract {
+    pub(crate) fn assert_mint_not_paused(&self) {
+        assert!(!self.paused.mint, "Minting is paused");
+    }
+
+    pub(crate) fn assert_transfers_not_paused(&self) {
+        assert!(!self.paused.transfers, "Transfers are paused");
+    }
+
+    pub(crate) fn assert_approvals_not_paused(&self) {
+        assert!(!self.paused.approvals, "Approvals are paused");
+    }
+}
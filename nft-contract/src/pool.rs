@@ -0,0 +1,118 @@
+/**
+ * Mint ngẫu nhiên từ pool metadata được owner nạp trước
+ * - Người mua nhận 1 metadata ngẫu nhiên chưa được mint (chọn bằng env::random_seed)
+ * - Metadata đã mint được xoá khỏi pool bằng swap_remove -> O(1) kể cả với collection lớn
+ * - token_id được contract tự sinh (giống nft_mint với token_id = None)
+ * - Người mint trả phí lưu trữ đầy đủ của token, storage của metadata được xoá khỏi pool refund cho người đã nạp metadata đó
+ */
+use crate::*;
+
+// 1 metadata trong pool kèm account đã trả phí lưu trữ khi nạp
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MintPoolEntry {
+    pub metadata: TokenMetadata,
+    pub payer_id: AccountId,
+}
+
+#[near_bindgen]
+impl Contract {
+    // Nạp thêm metadata vào pool (chỉ owner của contract), owner trả phí lưu trữ
+    #[payable]
+    pub fn nft_load_mint_pool(&mut self, metadata: Vec<TokenMetadata>) {
+        assert_at_least_one_yocto();
+        self.assert_owner();
+        assert!(!metadata.is_empty(), "Metadata must not be empty");
+        let payer_id = env::predecessor_account_id();
+
+        let before_storage_usage = env::storage_usage();
+
+        self.mint_pool_loaded += metadata.len() as u64;
+        for token_metadata in metadata {
+            token_metadata.assert_valid();
+            self.mint_pool.push(&MintPoolEntry {
+                metadata: token_metadata,
+                payer_id: payer_id.clone(),
+            });
+        }
+
+        self.internal_charge_storage(env::storage_usage() - before_storage_usage);
+    }
+
+    /**
+     * Mint 1 token với metadata ngẫu nhiên từ pool cho receiver_id
+     * - Chỉ owner của contract hoặc minter (còn quota, chưa hết hạn) mới được mint
+     * - Người gọi trả phí lưu trữ của token vừa mint (từ deposit hoặc số dư storage đã nạp)
+     * - Return token_id của token vừa mint
+     */
+    #[payable]
+    pub fn nft_mint_random(&mut self, receiver_id: AccountId) -> TokenId {
+        self.assert_mint_not_paused();
+        self.internal_use_mint_quota(&env::predecessor_account_id(), 1);
+
+        let (token_id, storage_usage) =
+            self.internal_mint_random(&receiver_id, &env::predecessor_account_id());
+
+        // NFT MINT LOG
+        let nft_mint_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftMint(vec![NftMintLog {
+                owner_id: receiver_id.to_string(),
+                token_ids: vec![token_id.clone()],
+                memo: None,
+            }]),
+        };
+        env::log(nft_mint_log.to_string().as_bytes());
+
+        self.internal_charge_storage(storage_usage);
+
+        token_id
+    }
+
+    // Số metadata còn lại trong pool (chưa được mint)
+    pub fn nft_mint_pool_remaining(&self) -> u64 {
+        self.mint_pool.len()
+    }
+
+    // Số metadata đã được mint từ pool
+    pub fn nft_mint_pool_minted(&self) -> u64 {
+        self.mint_pool_loaded - self.mint_pool.len()
+    }
+}
+
+impl Contract {
+    // Lấy ngẫu nhiên 1 metadata ra khỏi pool và mint cho receiver_id
+    // Refund storage của metadata được xoá khỏi pool cho người đã nạp metadata đó
+    // Không tính phí storage của token và không log event -> Hàm gọi sẽ tự xử lý
+    // Return token_id và số bytes storage của token để hàm gọi tính phí
+    pub(crate) fn internal_mint_random(
+        &mut self,
+        receiver_id: &AccountId,
+        storage_payer: &AccountId,
+    ) -> (TokenId, u64) {
+        let remaining = self.mint_pool.len();
+        assert!(remaining > 0, "Mint pool is empty");
+
+        // random_seed giống nhau trong cùng 1 block -> Trộn thêm số thứ tự token để các lần mint khác nhau
+        let token_id = self.internal_next_token_id();
+        let seed = env::sha256(&[env::random_seed(), token_id.as_bytes().to_vec()].concat());
+        let mut random_bytes = [0u8; 8];
+        random_bytes.copy_from_slice(&seed[..8]);
+        let index = u64::from_le_bytes(random_bytes) % remaining;
+
+        let before_storage_usage = env::storage_usage();
+        let entry = self.mint_pool.swap_remove(index);
+        refund_storage_released(entry.payer_id, before_storage_usage - env::storage_usage());
+
+        let storage_usage = self.internal_mint(
+            &token_id,
+            &entry.metadata,
+            receiver_id,
+            self.internal_royalty_for_mint(None),
+            None,
+            storage_payer,
+        );
+
+        (token_id, storage_usage)
+    }
+}
@@ -0,0 +1,149 @@
+/**
+ * Reveal metadata cho các đợt drop: mint với metadata tạm (placeholder), công bố metadata thật sau
+ * - Trước khi mint, owner commit Merkle root của toàn bộ metadata cuối cùng
+ * - Mỗi leaf = sha256(borsh((token_id, metadata))), mỗi node = sha256(node nhỏ hơn || node lớn hơn)
+ * - Owner công bố metadata theo từng đợt, mỗi token kèm Merkle proof -> Không thể đổi metadata sau khi đã commit
+ * - Sau khi commit, nft_metadata_update bị khoá -> nft_reveal là cách duy nhất để thay đổi metadata của token
+ */
+use crate::*;
+
+// Metadata thật của 1 token trong nft_reveal
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RevealArgs {
+    pub token_id: TokenId,
+    pub metadata: TokenMetadata,
+    pub proof: Vec<Base64VecU8>, // Các node anh em từ leaf tới root
+}
+
+#[near_bindgen]
+impl Contract {
+    // Commit Merkle root của metadata cuối cùng, chỉ được set 1 lần và trước khi mint token đầu tiên
+    #[payable]
+    pub fn set_reveal_commitment(&mut self, root: Base64VecU8) {
+        assert_one_yocto();
+        self.assert_owner();
+        assert!(
+            self.reveal_root.is_none(),
+            "Reveal commitment is already set"
+        );
+        assert!(
+            self.token_metadata_by_id.is_empty(),
+            "Reveal commitment must be set before minting starts"
+        );
+
+        self.reveal_root = Some(
+            root.0
+                .try_into()
+                .unwrap_or_else(|_| env::panic(b"Reveal commitment must be a 32-byte hash")),
+        );
+    }
+
+    pub fn get_reveal_commitment(&self) -> Option<Base64VecU8> {
+        self.reveal_root.map(|root| Base64VecU8(root.to_vec()))
+    }
+
+    /**
+     * Công bố metadata thật cho 1 đợt token (chỉ owner của contract)
+     * - Metadata của mỗi token phải khớp với commitment (kiểm tra bằng Merkle proof)
+     * - Phí storage tăng thêm tính cho owner (từ deposit hoặc số dư storage đã nạp)
+     * - Metadata nhỏ hơn lúc mint -> Phần chênh lệch refund cho người trả phí lưu trữ của từng token
+     * - Log event nft_reveal và nft_metadata_update cho cả đợt
+     */
+    #[payable]
+    pub fn nft_reveal(&mut self, tokens: Vec<RevealArgs>) {
+        assert_at_least_one_yocto();
+        self.assert_owner();
+        let root = self.reveal_root.expect("Reveal commitment is not set");
+        assert!(!tokens.is_empty(), "Batch must contain at least one token");
+
+        let before_storage_usage = env::storage_usage();
+
+        let mut released = 0;
+        let mut token_ids = Vec::new();
+        for args in tokens {
+            let token = self.tokens_by_id.get(&args.token_id).unwrap_or_else(|| {
+                env::panic(format!("Not found token {}", args.token_id).as_bytes())
+            });
+            assert!(
+                parse_edition_token_id(&args.token_id).is_none(),
+                "Edition metadata is shared by its series"
+            );
+            assert!(
+                verify_reveal_proof(&root, &args.token_id, &args.metadata, &args.proof),
+                "Metadata of token {} does not match the reveal commitment",
+                args.token_id
+            );
+            args.metadata.assert_valid();
+
+            let storage_payer = self.internal_storage_payer(&args.token_id, &token.owner_id);
+            released += self.internal_replace_token_metadata(
+                &args.token_id,
+                &TokenMetadata {
+                    updated_at: Some(env::block_timestamp() / 1_000_000),
+                    ..args.metadata
+                },
+                storage_payer,
+            );
+            token_ids.push(args.token_id);
+        }
+
+        // NFT REVEAL LOG
+        let nft_reveal_log: EventLog = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftReveal(vec![NftRevealLog {
+                token_ids: token_ids.clone(),
+            }]),
+        };
+        env::log(nft_reveal_log.to_string().as_bytes());
+
+        // NFT METADATA UPDATE LOG
+        let nft_metadata_update_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.1.0".to_string(),
+            event: EventLogVariant::NftMetadataUpdate(vec![NftMetadataUpdateLog {
+                token_ids,
+                memo: None,
+            }]),
+        };
+        env::log(nft_metadata_update_log.to_string().as_bytes());
+
+        // Phần đã refund cho người trả phí lưu trữ không tính vào thay đổi storage của owner
+        self.internal_charge_storage_change(before_storage_usage, env::storage_usage() + released);
+    }
+}
+
+// Leaf của cây Merkle ứng với metadata cuối cùng của token_id
+pub(crate) fn reveal_leaf(token_id: &TokenId, metadata: &TokenMetadata) -> CryptoHash {
+    sha256_hash(&(token_id, metadata).try_to_vec().unwrap())
+}
+
+// Node cha của 2 node, sắp xếp trước khi hash -> proof không cần chứa vị trí trái/phải
+pub(crate) fn reveal_node(a: &CryptoHash, b: &CryptoHash) -> CryptoHash {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    sha256_hash(&[left.as_ref(), right.as_ref()].concat())
+}
+
+fn sha256_hash(bytes: &[u8]) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(bytes));
+    hash
+}
+
+fn verify_reveal_proof(
+    root: &CryptoHash,
+    token_id: &TokenId,
+    metadata: &TokenMetadata,
+    proof: &[Base64VecU8],
+) -> bool {
+    let mut node = reveal_leaf(token_id, metadata);
+    for sibling in proof {
+        let sibling: CryptoHash = match sibling.0.as_slice().try_into() {
+            Ok(sibling) => sibling,
+            Err(_) => return false,
+        };
+        node = reveal_node(&node, &sibling);
+    }
+    &node == root
+}
@@ -5,67 +5,44 @@ use crate::*;
 pub struct Payout {
     pub payout: HashMap<AccountId, U128>,
 }
-pub trait NonFungibleTokenCore {
-    fn nft_payout(&self, token_id: String, balance: U128, max_len_payout: u32) -> Payout;
+
+// Các hàm theo chuẩn NEP-199 của NEAR - Royalties and Payouts
+// Xem thêm tại: https://nomicon.io/Standards/Tokens/NonFungibleToken/Payout
+pub trait NonFungibleTokenPayout {
+    fn nft_payout(&self, token_id: String, balance: U128, max_len_payout: Option<u32>) -> Payout;
 
     fn nft_transfer_payout(
         &mut self,
         receiver_id: AccountId,
         token_id: TokenId,
-        approval_id: u64,
-        memo: String,
+        approval_id: Option<u64>,
+        memo: Option<String>,
         balance: U128,
-        max_len_payout: u32,
+        max_len_payout: Option<u32>,
     ) -> Payout;
 }
 
 #[near_bindgen]
-impl NonFungibleTokenCore for Contract {
-    fn nft_payout(&self, token_id: String, balance: U128, max_len_payout: u32) -> Payout {
+impl NonFungibleTokenPayout for Contract {
+    // Tính payout của token với balance cho trước (không transfer token)
+    fn nft_payout(&self, token_id: String, balance: U128, max_len_payout: Option<u32>) -> Payout {
         let token = self.tokens_by_id.get(&token_id).expect("Not found token");
 
-        let owner_id = token.owner_id;
-        let mut total_perpetual = 0;
-        let balance_u128 = u128::from(balance);
-        let mut payout_object = Payout {
-            payout: HashMap::new(),
-        };
-        let royalty = token.royalty;
-        assert!(
-            royalty.len() as u32 <= max_len_payout,
-            "Market cannot payout to that many receivers"
-        );
-
-        for (k, v) in royalty.iter() {
-            let key = k.clone();
-
-            if key != owner_id {
-                //
-                payout_object
-                    .payout
-                    .insert(key, royalty_to_payout(*v, balance_u128));
-                total_perpetual += *v;
-            }
-        }
-
-        payout_object.payout.insert(
-            owner_id,
-            royalty_to_payout(10000 - total_perpetual, balance_u128),
-        );
-
-        payout_object
+        compute_payout(&token.owner_id, &token.royalty, balance.0, max_len_payout)
     }
 
+    // Transfer token và trả về payout cho owner cũ và các account nhận royalty
     #[payable]
     fn nft_transfer_payout(
         &mut self,
         receiver_id: AccountId,
         token_id: TokenId,
-        approval_id: u64,
-        memo: String,
+        approval_id: Option<u64>,
+        memo: Option<String>,
         balance: U128,
-        max_len_payout: u32,
+        max_len_payout: Option<u32>,
     ) -> Payout {
+        self.assert_transfers_not_paused();
         assert_one_yocto();
 
         let sender_id = env::predecessor_account_id();
@@ -73,42 +50,267 @@ impl NonFungibleTokenCore for Contract {
             &sender_id,
             &receiver_id,
             &token_id,
-            Some(approval_id),
-            Some(memo),
+            approval_id,
+            memo.clone(),
+        );
+        // Ghi lại lịch sử transfer kèm giá bán, phí lưu trữ do sender (thường là market) trả
+        self.internal_record_transfer(
+            &token_id,
+            &previous_token.owner_id,
+            &receiver_id,
+            memo,
+            Some(balance),
         );
-        refund_approved_account_ids(
+        self.internal_refund_previous_approvals(
+            &token_id,
             previous_token.owner_id.clone(),
             &previous_token.approved_account_ids,
         );
-        let owner_id = previous_token.owner_id;
-        let mut total_perpetual = 0;
-        let balance_u128 = u128::from(balance);
-        let mut payout_object = Payout {
-            payout: HashMap::new(),
-        };
 
-        let royalty = previous_token.royalty;
+        compute_payout(
+            &previous_token.owner_id,
+            &previous_token.royalty,
+            balance.0,
+            max_len_payout,
+        )
+    }
+}
+
+/**
+ * Tính payout theo chuẩn NEP-199
+ * - Mỗi account nhận royalty (trừ owner) nhận balance * royalty / 10000
+ * - Owner nhận phần còn lại, bao gồm phần lẻ do làm tròn
+ * - Nếu owner cũng có trong royalty -> Gộp vào phần của owner (chỉ có 1 entry cho owner)
+ * - Số lượng entry trong payout (tính cả owner) không được vượt quá max_len_payout
+ */
+pub(crate) fn compute_payout(
+    owner_id: &AccountId,
+    royalty: &HashMap<AccountId, u32>,
+    balance: Balance,
+    max_len_payout: Option<u32>,
+) -> Payout {
+    // Sắp xếp theo account để kết quả không phụ thuộc vào thứ tự của HashMap
+    let mut royalty_accounts: Vec<(&AccountId, &u32)> = royalty
+        .iter()
+        .filter(|(account_id, _)| *account_id != owner_id)
+        .collect();
+    royalty_accounts.sort();
+
+    if let Some(max_len_payout) = max_len_payout {
+        // Tính cả entry của owner
         assert!(
-            royalty.len() as u32 <= max_len_payout,
+            royalty_accounts.len() < max_len_payout as usize,
             "Market cannot payout to that many receivers"
         );
+    }
+
+    let mut payout = HashMap::new();
+    let mut total_payout: Balance = 0;
+    for (account_id, royalty_percentage) in royalty_accounts {
+        // Tổng royalty không bao giờ vượt quá balance (kể cả token có royalty > 100% từ trước)
+        let amount = royalty_to_payout(*royalty_percentage, balance).min(balance - total_payout);
+        payout.insert(account_id.clone(), U128(amount));
+        total_payout += amount;
+    }
+
+    // Owner nhận phần còn lại (bao gồm cả phần lẻ do làm tròn)
+    payout.insert(owner_id.clone(), U128(balance - total_payout));
+
+    Payout { payout }
+}
+
+#[near_bindgen]
+impl Contract {
+    // Cập nhật tổng royalty tối đa (basis points) cho các token mint sau này
+    // Không được thấp hơn tổng royalty mặc định, nếu không các lần mint dùng royalty mặc định sẽ bị lỗi
+    #[payable]
+    pub fn set_max_royalty(&mut self, max_royalty: u32) {
+        assert_one_yocto();
+        self.assert_owner();
+        assert!(
+            max_royalty <= 10_000,
+            "Max royalty cannot exceed 10000 (100%)"
+        );
+        let default_royalty_total: u64 =
+            self.default_royalty.values().copied().map(u64::from).sum();
+        assert!(
+            default_royalty_total <= u64::from(max_royalty),
+            "Max royalty cannot be lower than the default royalty of {}",
+            default_royalty_total
+        );
+
+        let old_max_royalty = std::mem::replace(&mut self.max_royalty, max_royalty);
+
+        // MAX ROYALTY UPDATE LOG
+        let max_royalty_log: EventLog = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::MaxRoyaltyUpdate(vec![MaxRoyaltyUpdateLog {
+                old_max_royalty,
+                new_max_royalty: max_royalty,
+            }]),
+        };
+        env::log(max_royalty_log.to_string().as_bytes());
+    }
+
+    pub fn get_max_royalty(&self) -> u32 {
+        self.max_royalty
+    }
+
+    /**
+     * Cập nhật royalty mặc định (chỉ owner của contract)
+     * - Áp dụng cho các token mint sau này nếu lúc mint không truyền perpetual_royalties
+     * - Royalty được copy vào từng token khi mint -> Không thay đổi royalty của các token đã có
+     */
+    #[payable]
+    pub fn set_default_royalty(&mut self, royalty: HashMap<AccountId, u32>) {
+        assert_one_yocto();
+        self.assert_owner();
+
+        self.default_royalty = royalty_from_perpetual_royalties(Some(royalty), self.max_royalty);
+
+        // DEFAULT ROYALTY UPDATE LOG
+        let default_royalty_log: EventLog = EventLog {
+            standard: "zng-nft".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DefaultRoyaltyUpdate(vec![DefaultRoyaltyUpdateLog {
+                royalty: self.default_royalty.clone(),
+            }]),
+        };
+        env::log(default_royalty_log.to_string().as_bytes());
+    }
+
+    pub fn get_default_royalty(&self) -> HashMap<AccountId, u32> {
+        self.default_royalty.clone()
+    }
+
+    // Royalty đang áp dụng cho token (đã được cố định khi mint)
+    pub fn nft_royalty(&self, token_id: TokenId) -> HashMap<AccountId, u32> {
+        self.tokens_by_id
+            .get(&token_id)
+            .expect("Not found token")
+            .royalty
+    }
+}
+
+impl Contract {
+    // Royalty cho token mới: perpetual_royalties truyền vào khi mint, None -> Dùng royalty mặc định
+    pub(crate) fn internal_royalty_for_mint(
+        &self,
+        perpetual_royalties: Option<HashMap<AccountId, u32>>,
+    ) -> HashMap<AccountId, u32> {
+        royalty_from_perpetual_royalties(
+            perpetual_royalties.or_else(|| Some(self.default_royalty.clone())),
+            self.max_royalty,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn royalty(royalties: &[(&str, u32)]) -> HashMap<AccountId, u32> {
+        royalties
+            .iter()
+            .map(|(account_id, amount)| (account_id.to_string(), *amount))
+            .collect()
+    }
+
+    #[test]
+    fn payout_splits_royalty_and_owner() {
+        let owner_id = "owner.near".to_string();
+        let payout = compute_payout(
+            &owner_id,
+            &royalty(&[("artist.near", 1_000), ("platform.near", 250)]),
+            10_000,
+            Some(3),
+        )
+        .payout;
+
+        assert_eq!(payout.len(), 3);
+        assert_eq!(payout["artist.near"], U128(1_000));
+        assert_eq!(payout["platform.near"], U128(250));
+        assert_eq!(payout["owner.near"], U128(8_750));
+    }
+
+    #[test]
+    fn payout_zero_balance() {
+        let owner_id = "owner.near".to_string();
+        let payout = compute_payout(&owner_id, &royalty(&[("artist.near", 1_000)]), 0, None).payout;
+
+        assert_eq!(payout["artist.near"], U128(0));
+        assert_eq!(payout["owner.near"], U128(0));
+    }
+
+    #[test]
+    fn payout_one_yocto_goes_to_owner() {
+        let owner_id = "owner.near".to_string();
+        let payout = compute_payout(&owner_id, &royalty(&[("artist.near", 5_000)]), 1, None).payout;
+
+        assert_eq!(payout["artist.near"], U128(0));
+        assert_eq!(payout["owner.near"], U128(1));
+    }
+
+    #[test]
+    fn payout_rounding_dust_goes_to_owner() {
+        let owner_id = "owner.near".to_string();
+        let payout = compute_payout(
+            &owner_id,
+            &royalty(&[("artist.near", 3_333), ("platform.near", 3_333)]),
+            10,
+            None,
+        )
+        .payout;
+
+        assert_eq!(payout["artist.near"], U128(3));
+        assert_eq!(payout["platform.near"], U128(3));
+        assert_eq!(payout["owner.near"], U128(4));
+    }
+
+    #[test]
+    fn payout_owner_is_royalty_holder() {
+        let owner_id = "artist.near".to_string();
+        let payout = compute_payout(
+            &owner_id,
+            &royalty(&[("artist.near", 1_000), ("platform.near", 500)]),
+            10_000,
+            Some(2),
+        )
+        .payout;
 
-        for (k, v) in royalty.iter() {
-            //get the key
-            let key = k.clone();
-            if key != owner_id {
-                //
-                payout_object
-                    .payout
-                    .insert(key, royalty_to_payout(*v, balance_u128));
-                total_perpetual += *v;
-            }
-        }
-        payout_object.payout.insert(
-            owner_id,
-            royalty_to_payout(10000 - total_perpetual, balance_u128),
+        // Owner chỉ có 1 entry, nhận toàn bộ phần không thuộc về các account khác
+        assert_eq!(payout.len(), 2);
+        assert_eq!(payout["platform.near"], U128(500));
+        assert_eq!(payout["artist.near"], U128(9_500));
+    }
+
+    #[test]
+    #[should_panic(expected = "Market cannot payout to that many receivers")]
+    fn payout_max_len_includes_owner() {
+        let owner_id = "owner.near".to_string();
+        compute_payout(
+            &owner_id,
+            &royalty(&[("artist.near", 1_000), ("platform.near", 250)]),
+            10_000,
+            Some(2),
         );
+    }
+
+    #[test]
+    fn payout_never_exceeds_balance() {
+        // Token cũ có tổng royalty > 100%
+        let owner_id = "owner.near".to_string();
+        let payout = compute_payout(
+            &owner_id,
+            &royalty(&[("artist.near", 8_000), ("platform.near", 8_000)]),
+            100,
+            None,
+        )
+        .payout;
 
-        payout_object
+        assert_eq!(payout["artist.near"], U128(80));
+        assert_eq!(payout["platform.near"], U128(20));
+        assert_eq!(payout["owner.near"], U128(0));
     }
 }
@@ -0,0 +1,171 @@
+/**
+ * Series: Creator định nghĩa metadata, royalty và số lượng bản (copies) tối đa 1 lần
+ * Sau đó mint các edition với token_id có dạng `series_id:edition_number`
+ * Các edition dùng chung metadata của series -> Không phải lưu lại toàn bộ metadata cho mỗi token
+ */
+use crate::*;
+
+pub type SeriesId = u64;
+
+// Ký tự phân cách giữa series_id và edition_number trong token_id của edition
+pub const SERIES_DELIMITER: char = ':';
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Series {
+    // Người tạo series, chỉ người này mới được mint edition
+    pub creator_id: AccountId,
+    // Metadata dùng chung, `copies` là số lượng edition tối đa (None -> Không giới hạn)
+    pub metadata: TokenMetadata,
+    pub royalty: HashMap<AccountId, u32>,
+    // Số edition đã mint (kể cả edition đã bị burn), dùng để đánh số edition tiếp theo
+    pub minted: u64,
+    // Các edition của series là token soulbound, creator là issuer
+    pub soulbound: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonSeries {
+    pub series_id: SeriesId,
+    pub creator_id: AccountId,
+    pub metadata: TokenMetadata,
+    pub royalty: HashMap<AccountId, u32>,
+    pub minted: u64,
+    pub soulbound: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    /**
+     * Tạo series mới, chỉ owner của contract hoặc minter mới được tạo
+     * - metadata.copies: số lượng edition tối đa của series
+     * - soulbound = true -> Các edition không thể transfer, creator có quyền burn edition
+     * - Yêu cầu user nạp tiền để cover phí lưu trữ, refund lại phần thừa
+     * - Return series_id
+     */
+    #[payable]
+    pub fn nft_create_series(
+        &mut self,
+        metadata: TokenMetadata,
+        perpetual_royalties: Option<HashMap<AccountId, u32>>,
+        soulbound: Option<bool>,
+    ) -> SeriesId {
+        self.internal_assert_minter(&env::predecessor_account_id());
+
+        let before_storage_usage = env::storage_usage();
+
+        metadata.assert_valid();
+
+        let series_id = self.next_series_id;
+        self.next_series_id += 1;
+
+        let series = Series {
+            creator_id: env::predecessor_account_id(),
+            metadata,
+            royalty: self.internal_royalty_for_mint(perpetual_royalties),
+            minted: 0,
+            soulbound: soulbound.unwrap_or(false),
+        };
+        self.series_by_id.insert(&series_id, &series);
+
+        self.internal_charge_storage(env::storage_usage() - before_storage_usage);
+
+        series_id
+    }
+
+    /**
+     * Mint 1 edition của series cho receiver_id
+     * - Chỉ creator của series mới được mint, và creator vẫn phải là minter
+     * - Không được mint quá số copies của series
+     * - Return token_id của edition vừa mint
+     */
+    #[payable]
+    pub fn nft_mint_edition(&mut self, series_id: SeriesId, receiver_id: AccountId) -> TokenId {
+        self.assert_mint_not_paused();
+
+        let mut series = self.series_by_id.get(&series_id).expect("Not found series");
+        assert_eq!(
+            env::predecessor_account_id(),
+            series.creator_id,
+            "Only series creator can mint editions"
+        );
+        self.internal_use_mint_quota(&series.creator_id, 1);
+        if let Some(copies) = series.metadata.copies {
+            assert!(series.minted < copies, "Series is sold out");
+        }
+
+        series.minted += 1;
+        let token_id = format!("{}{}{}", series_id, SERIES_DELIMITER, series.minted);
+
+        // Edition chỉ lưu các thông tin riêng, phần còn lại lấy từ series khi gọi nft_token
+        let edition_metadata = TokenMetadata {
+            issued_at: Some(env::block_timestamp() / 1_000_000),
+            ..Default::default()
+        };
+        let storage_usage = self.internal_mint(
+            &token_id,
+            &edition_metadata,
+            &receiver_id,
+            series.royalty.clone(),
+            series.soulbound.then_some(&series.creator_id),
+            &series.creator_id,
+        );
+        self.series_by_id.insert(&series_id, &series);
+
+        // NFT MINT LOG
+        let nft_mint_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftMint(vec![NftMintLog {
+                owner_id: receiver_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+                memo: None,
+            }]),
+        };
+        env::log(nft_mint_log.to_string().as_bytes());
+
+        self.internal_charge_storage(storage_usage);
+
+        token_id
+    }
+
+    // Lấy thông tin series
+    pub fn nft_series(&self, series_id: SeriesId) -> Option<JsonSeries> {
+        self.series_by_id.get(&series_id).map(|series| JsonSeries {
+            series_id,
+            creator_id: series.creator_id,
+            metadata: series.metadata,
+            royalty: series.royalty,
+            minted: series.minted,
+            soulbound: series.soulbound,
+        })
+    }
+
+    // Lấy danh sách series (có pagination)
+    pub fn nft_series_list(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonSeries> {
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        self.series_by_id
+            .keys()
+            .skip(start as usize) // Pagination
+            .take(limit.unwrap_or(0) as usize) // Pagination
+            .map(|series_id| self.nft_series(series_id).unwrap())
+            .collect()
+    }
+}
+
+// token_id có dạng `series_id:edition_number` được dành riêng cho edition của series
+pub(crate) fn assert_not_edition_token_id(token_id: &str) {
+    assert!(
+        !token_id.contains(SERIES_DELIMITER),
+        "Token id cannot contain '{}'",
+        SERIES_DELIMITER
+    );
+}
+
+// Tách token_id của edition thành (series_id, edition_number)
+// Return None nếu token_id không phải là edition
+pub(crate) fn parse_edition_token_id(token_id: &str) -> Option<(SeriesId, u64)> {
+    let (series_id, edition) = token_id.split_once(SERIES_DELIMITER)?;
+    Some((series_id.parse().ok()?, edition.parse().ok()?))
+}
@@ -0,0 +1,211 @@
+/**
+ * Quản lý storage theo chuẩn NEP-145
+ * - User (hoặc backend) nạp trước NEAR vào số dư storage của account 1 lần
+ * - Khi mint/approve, phí storage được lấy từ số tiền đính kèm trước, phần còn thiếu trừ vào số dư đã nạp
+ * - Số dư còn lại có thể rút ra bất cứ lúc nào
+ */
+use crate::*;
+
+// Số bytes để lưu 1 entry trong storage_balances: account id (tối đa 64 bytes) + balance (16 bytes) + overhead của 1 record (40 bytes) + prefix
+const ACCOUNT_STORAGE_BYTES: u64 = 125;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    pub total: U128,
+    pub available: U128,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+    pub min: U128,
+    pub max: Option<U128>,
+}
+
+pub trait StorageManagement {
+    // Nạp tiền vào số dư storage của account_id (mặc định là người gọi)
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance;
+    // Rút số dư storage chưa dùng tới (mặc định rút toàn bộ)
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance;
+    // Huỷ đăng ký và rút toàn bộ số dư, return false nếu account chưa đăng ký
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool;
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds;
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance>;
+}
+
+#[near_bindgen]
+impl StorageManagement for Contract {
+    /**
+     * - Account chưa đăng ký: phải nạp tối thiểu storage_balance_bounds().min, phần này bị giữ lại tới khi huỷ đăng ký
+     * - registration_only = true: chỉ giữ lại phần tối thiểu để đăng ký, refund phần thừa
+     */
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let deposit = env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let min_balance = storage_balance_min();
+
+        let (available, refund) = match self.storage_balances.get(&account_id) {
+            // Đã đăng ký, registration_only -> Không cần nạp thêm
+            Some(available) if registration_only.unwrap_or(false) => (available, deposit),
+            Some(available) => (available + deposit, 0),
+            None => {
+                assert!(
+                    deposit >= min_balance,
+                    "Must attach at least {} yoctoNear to register",
+                    min_balance
+                );
+                if registration_only.unwrap_or(false) {
+                    (0, deposit - min_balance)
+                } else {
+                    (deposit - min_balance, 0)
+                }
+            }
+        };
+
+        self.storage_balances.insert(&account_id, &available);
+        if refund > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+
+        storage_balance_of(available)
+    }
+
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+
+        let available = self
+            .storage_balances
+            .get(&account_id)
+            .expect("Account is not registered");
+        let amount = amount.map(|amount| amount.0).unwrap_or(available);
+        assert!(
+            amount <= available,
+            "Cannot withdraw more than the available balance {}",
+            available
+        );
+
+        self.storage_balances
+            .insert(&account_id, &(available - amount));
+        Promise::new(account_id).transfer(amount + 1);
+
+        storage_balance_of(available - amount)
+    }
+
+    // Phí storage đã dùng cho token/approval thuộc về token (được refund khi burn/revoke)
+    // -> Không có dữ liệu nào gắn với account cần xoá, force không ảnh hưởng
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+        let _ = force;
+        let account_id = env::predecessor_account_id();
+
+        match self.storage_balances.remove(&account_id) {
+            Some(available) => {
+                Promise::new(account_id).transfer(available + storage_balance_min() + 1);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: U128(storage_balance_min()),
+            max: None,
+        }
+    }
+
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.storage_balances
+            .get(&account_id)
+            .map(storage_balance_of)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    // Account nhận refund phí lưu trữ của token (người trả phí khi mint, token cũ chưa lưu thì là owner)
+    pub fn nft_storage_payer(&self, token_id: TokenId) -> AccountId {
+        let token = self.tokens_by_id.get(&token_id).expect("Not found token");
+        self.internal_storage_payer(&token_id, &token.owner_id)
+    }
+}
+
+impl Contract {
+    // Thu phí storage cho storage_used bytes của người gọi, lấy từ số tiền đính kèm
+    pub(crate) fn internal_charge_storage(&mut self, storage_used: u64) {
+        self.internal_charge_storage_from(
+            &env::predecessor_account_id(),
+            env::attached_deposit(),
+            storage_used,
+        );
+    }
+
+    /**
+     * Thu phí storage cho storage_used bytes của account_id
+     * - Lấy từ deposit trước, phần còn thiếu trừ vào số dư storage đã nạp của account_id
+     * - Refund lại phần deposit thừa cho account_id
+     */
+    pub(crate) fn internal_charge_storage_from(
+        &mut self,
+        account_id: &AccountId,
+        deposit: Balance,
+        storage_used: u64,
+    ) {
+        let required_cost = env::storage_byte_cost() * Balance::from(storage_used);
+
+        if deposit >= required_cost {
+            let refund_amount = deposit - required_cost;
+            if refund_amount > 1 {
+                Promise::new(account_id.clone()).transfer(refund_amount);
+            }
+            return;
+        }
+
+        let shortfall = required_cost - deposit;
+        let available = self.storage_balances.get(account_id).unwrap_or(0);
+        assert!(
+            available >= shortfall,
+            "Must attach {} yoctoNear or prepay storage to cover storage",
+            required_cost
+        );
+        self.storage_balances
+            .insert(account_id, &(available - shortfall));
+    }
+
+    // Giống refund_storage_change, nhưng phần tăng thêm được thu qua internal_charge_storage
+    pub(crate) fn internal_charge_storage_change(
+        &mut self,
+        before_storage_usage: u64,
+        after_storage_usage: u64,
+    ) {
+        if after_storage_usage >= before_storage_usage {
+            self.internal_charge_storage(after_storage_usage - before_storage_usage);
+        } else {
+            refund_storage_change(before_storage_usage, after_storage_usage);
+        }
+    }
+}
+
+fn storage_balance_min() -> Balance {
+    Balance::from(ACCOUNT_STORAGE_BYTES) * env::storage_byte_cost()
+}
+
+fn storage_balance_of(available: Balance) -> StorageBalance {
+    StorageBalance {
+        total: U128(available + storage_balance_min()),
+        available: U128(available),
+    }
+}
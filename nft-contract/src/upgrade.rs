@@ -0,0 +1,145 @@
+/**
+ * Upgrade code của contract và migrate state
+ * - Owner gọi `upgrade` với input là code (wasm) mới -> Deploy code mới và gọi `migrate`
+ * - `migrate` đọc state cũ theo version đang lưu và chuyển sang layout hiện tại
+ */
+use crate::*;
+
+// Version của layout state hiện tại
+// Note: Khi thay đổi layout của Contract ở bản release mới -> Freeze layout đang chạy thành ContractV2,
+// tăng STATE_VERSION và thêm variant vào VersionedContract (chỉ tăng 1 lần cho mỗi bản release)
+pub const STATE_VERSION: u16 = 2;
+
+// Key lưu version của state đang lưu trên chain
+// State được tạo trước khi có versioning sẽ không có key này -> version 1
+pub(crate) const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+const STATE_KEY: &[u8] = b"STATE";
+
+// Gas giữ lại để hoàn thành hàm upgrade sau khi tạo promise gọi migrate
+const GAS_FOR_UPGRADE: Gas = 10_000_000_000_000;
+const NO_DEPOSIT: Balance = 0;
+
+// Layout state của bản deploy đầu tiên (chưa có versioning)
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    pub owner_id: AccountId,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub tokens_by_id: LookupMap<TokenId, Token>,
+    pub token_metadata_by_id: UnorderedMap<TokenId, TokenMetadata>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+}
+
+// Tất cả các layout state mà contract có thể đọc được
+pub enum VersionedContract {
+    V1(ContractV1),
+    Current(Box<Contract>),
+}
+
+impl From<VersionedContract> for Contract {
+    fn from(state: VersionedContract) -> Self {
+        match state {
+            VersionedContract::V1(state) => Contract {
+                owner_id: state.owner_id,
+                pending_owner_id: None,
+                tokens_per_owner: state.tokens_per_owner,
+                tokens_by_id: state.tokens_by_id,
+                token_metadata_by_id: state.token_metadata_by_id,
+                metadata: state.metadata,
+                series_by_id: UnorderedMap::new(StorageKey::SeriesByIdKey.try_to_vec().unwrap()),
+                next_series_id: 0,
+                minters: UnorderedMap::new(StorageKey::MintersKey.try_to_vec().unwrap()),
+                max_royalty: DEFAULT_MAX_ROYALTY,
+                soulbound_tokens: LookupMap::new(
+                    StorageKey::SoulboundTokensKey.try_to_vec().unwrap(),
+                ),
+                issuer_transfers_expired: false,
+                approval_expirations: LookupMap::new(
+                    StorageKey::ApprovalExpirationsKey.try_to_vec().unwrap(),
+                ),
+                operators: LookupMap::new(StorageKey::OperatorsKey.try_to_vec().unwrap()),
+                paused: PauseStatus::default(),
+                storage_balances: LookupMap::new(
+                    StorageKey::StorageBalancesKey.try_to_vec().unwrap(),
+                ),
+                token_history: LookupMap::new(StorageKey::TokenHistoryKey.try_to_vec().unwrap()),
+                next_token_id: 0,
+                token_id_prefix: String::new(),
+                reveal_root: None,
+                mint_pool: Vector::new(StorageKey::MintPoolKey.try_to_vec().unwrap()),
+                mint_pool_loaded: 0,
+                drop_config: None,
+                drop_allowlist: LookupSet::new(StorageKey::DropAllowlistKey.try_to_vec().unwrap()),
+                drop_wallet_minted: LookupMap::new(
+                    StorageKey::DropWalletMintedKey.try_to_vec().unwrap(),
+                ),
+                drop_treasury_near: 0,
+                drop_treasury_ft: UnorderedMap::new(
+                    StorageKey::DropTreasuryFtKey.try_to_vec().unwrap(),
+                ),
+                token_storage_payers: LookupMap::new(
+                    StorageKey::TokenStoragePayersKey.try_to_vec().unwrap(),
+                ),
+                default_royalty: HashMap::new(),
+                token_metadata_growth: LookupMap::new(
+                    StorageKey::TokenMetadataGrowthKey.try_to_vec().unwrap(),
+                ),
+            },
+            VersionedContract::Current(state) => *state,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    // Input của hàm này là code (wasm) mới, không phải JSON
+    pub fn upgrade(&self) {
+        self.assert_owner();
+
+        let code = env::input().expect("Not found code to deploy");
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                b"migrate".to_vec(),
+                vec![],
+                NO_DEPOSIT,
+                env::prepaid_gas() - env::used_gas() - GAS_FOR_UPGRADE,
+            )
+            .as_return();
+    }
+
+    // Được gọi bởi chính contract sau khi deploy code mới
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let state = env::storage_read(STATE_KEY).expect("Not found state to migrate");
+
+        let versioned_state = match read_state_version() {
+            1 => VersionedContract::V1(
+                ContractV1::try_from_slice(&state).expect("Cannot deserialize state V1"),
+            ),
+            STATE_VERSION => VersionedContract::Current(Box::new(
+                Contract::try_from_slice(&state).expect("Cannot deserialize current state"),
+            )),
+            version => env::panic(format!("Unknown state version {}", version).as_bytes()),
+        };
+
+        write_state_version();
+        Contract::from(versioned_state)
+    }
+
+    // Version của state mà contract đang chạy
+    pub fn version(&self) -> u16 {
+        read_state_version()
+    }
+}
+
+pub(crate) fn read_state_version() -> u16 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|version| u16::try_from_slice(&version).expect("Cannot deserialize state version"))
+        .unwrap_or(1)
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &STATE_VERSION.try_to_vec().unwrap());
+}
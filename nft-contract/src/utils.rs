@@ -30,6 +30,28 @@ pub(crate) fn refund_deposit(storage_used: u64) {
     }
 }
 
+// Tính phí storage khi dữ liệu thay đổi kích thước
+// - Tăng -> Yêu cầu user nạp tiền để cover phần tăng thêm, refund phần thừa
+// - Giảm -> Refund phí của phần storage được giải phóng và toàn bộ tiền đã deposit
+pub(crate) fn refund_storage_change(before_storage_usage: u64, after_storage_usage: u64) {
+    if after_storage_usage >= before_storage_usage {
+        refund_deposit(after_storage_usage - before_storage_usage);
+    } else {
+        let storage_released = before_storage_usage - after_storage_usage;
+        Promise::new(env::predecessor_account_id()).transfer(
+            Balance::from(storage_released) * env::storage_byte_cost() + env::attached_deposit(),
+        );
+    }
+}
+
+// Refund phí storage được giải phóng cho account_id
+pub(crate) fn refund_storage_released(account_id: AccountId, storage_released: u64) {
+    if storage_released > 0 {
+        Promise::new(account_id)
+            .transfer(Balance::from(storage_released) * env::storage_byte_cost());
+    }
+}
+
 pub(crate) fn assert_one_yocto() {
     assert_eq!(
         env::attached_deposit(),
@@ -67,6 +89,48 @@ where
 pub(crate) fn refund_approved_account_ids(sender_id: AccountId, approved_account_ids: &HashMap<AccountId, u64>) {
     refund_approved_account_ids_iter(sender_id, approved_account_ids.keys());
 }
-pub(crate) fn royalty_to_payout(royalty_percentage: u32, amount_to_pay: Balance) -> U128 {
-    U128(royalty_percentage as u128 * amount_to_pay / 10_000u128)
+
+// Tạo royalty map từ perpetual_royalties truyền vào khi mint
+// Royalty tính theo basis points (10000 = 100%), tổng royalty không được vượt quá max_royalty
+pub(crate) fn royalty_from_perpetual_royalties(
+    perpetual_royalties: Option<HashMap<AccountId, u32>>,
+    max_royalty: u32,
+) -> HashMap<AccountId, u32> {
+    let mut royalty = HashMap::new();
+
+    // if perpetual royalties were passed into the function:
+    if let Some(perpetual_royalties) = perpetual_royalties {
+        //make sure that the length of the perpetual royalties is below 7 since we won't have enough GAS to pay out that many people
+        assert!(
+            perpetual_royalties.len() < 7,
+            "Cannot add more than 6 perpetual royalty amounts"
+        );
+
+        let mut total_royalty: u64 = 0;
+        //iterate through the perpetual royalties and insert the account and amount in the royalty map
+        for (account, amount) in perpetual_royalties {
+            assert!(amount > 0, "Royalty of {} must be greater than 0", account);
+            total_royalty += u64::from(amount);
+            royalty.insert(account, amount);
+        }
+
+        assert!(
+            total_royalty <= u64::from(max_royalty),
+            "Total royalty {} exceeds the maximum of {}",
+            total_royalty,
+            max_royalty
+        );
+    }
+
+    royalty
+}
+
+// Tính số tiền royalty = amount_to_pay * royalty_percentage / 10000
+// Tách amount_to_pay theo 10000 để phép nhân không bị tràn với balance lớn
+pub(crate) fn royalty_to_payout(royalty_percentage: u32, amount_to_pay: Balance) -> Balance {
+    let royalty_percentage = Balance::from(royalty_percentage);
+
+    (amount_to_pay / 10_000)
+        .saturating_mul(royalty_percentage)
+        .saturating_add(amount_to_pay % 10_000 * royalty_percentage / 10_000)
 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+/**
+ * Thời gian hiệu lực của token theo metadata starts_at / expires_at (ex. vé sự kiện, membership)
+ * - Token đã hết hạn không thể transfer hoặc approve (issuer có thể được phép chuyển nếu owner contract bật)
+ * - Bất kỳ ai cũng có thể burn token đã hết hạn để dọn dẹp storage
+ */
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    // Token có đang trong thời gian hiệu lực hay không
+    pub fn nft_is_valid(&self, token_id: TokenId) -> bool {
+        assert!(self.tokens_by_id.contains_key(&token_id), "Not found token");
+
+        self.internal_token_metadata(&token_id)
+            .is_valid(env::block_timestamp() / 1_000_000)
+    }
+
+    /**
+     * Burn các token đã hết hạn, ai cũng có thể gọi
+     * - Phí storage được giải phóng và phí approve được refund cho người trả phí lưu trữ của token (mặc định là owner)
+     */
+    pub fn nft_burn_expired(&mut self, token_ids: Vec<TokenId>) {
+        assert!(!token_ids.is_empty(), "Token ids must not be empty");
+
+        for token_id in token_ids.iter() {
+            assert!(
+                self.tokens_by_id.contains_key(token_id),
+                "Not found token {}",
+                token_id
+            );
+            assert!(
+                self.internal_is_expired(token_id),
+                "Token {} has not expired",
+                token_id
+            );
+
+            self.internal_burn(token_id, None, None);
+        }
+    }
+
+    // Cho phép / không cho phép issuer chuyển token đã hết hạn
+    #[payable]
+    pub fn set_issuer_transfers_expired(&mut self, allowed: bool) {
+        assert_one_yocto();
+        self.assert_owner();
+
+        self.issuer_transfers_expired = allowed;
+    }
+
+    pub fn get_issuer_transfers_expired(&self) -> bool {
+        self.issuer_transfers_expired
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_is_expired(&self, token_id: &TokenId) -> bool {
+        self.internal_token_metadata(token_id)
+            .is_expired(env::block_timestamp() / 1_000_000)
+    }
+
+    // Issuer của token: issuer của token soulbound, creator của series (với edition), hoặc owner của contract
+    pub(crate) fn internal_token_issuer(&self, token_id: &TokenId) -> AccountId {
+        if let Some(issuer_id) = self.soulbound_tokens.get(token_id) {
+            return issuer_id;
+        }
+
+        parse_edition_token_id(token_id)
+            .and_then(|(series_id, _)| self.series_by_id.get(&series_id))
+            .map(|series| series.creator_id)
+            .unwrap_or_else(|| self.owner_id.clone())
+    }
+}